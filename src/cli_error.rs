@@ -0,0 +1,98 @@
+//! Machine-readable CLI error type
+//!
+//! Every command function returns `Result<(), CliError>` so `main` can map
+//! failures onto a stable exit code, letting scripts branch on why a
+//! command failed instead of scraping stderr text.
+
+use thiserror::Error;
+
+/// A CLI-level error, carrying enough information for `main` to pick an
+/// exit code. Commands convert their underlying errors (`VaultError`,
+/// `io::Error`, `ClipboardError`, ...) into this via the `From` impls below.
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    AuthFailed(String),
+    #[error("{0}")]
+    VaultMissing(String),
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CliError {
+    /// Process exit code for this error. Stable across releases so scripts
+    /// can branch on it instead of matching stderr text.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::NotFound(_) => 2,
+            CliError::AuthFailed(_) => 3,
+            CliError::VaultMissing(_) => 4,
+            CliError::Io(_) => 5,
+            CliError::Other(_) => 1,
+        }
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(e: serde_json::Error) -> Self {
+        CliError::Other(e.to_string())
+    }
+}
+
+impl From<crate::vault::VaultError> for CliError {
+    fn from(e: crate::vault::VaultError) -> Self {
+        use crate::vault::VaultError;
+        match e {
+            VaultError::NotInitialized => CliError::VaultMissing(e.to_string()),
+            VaultError::WrongPassword => CliError::AuthFailed(e.to_string()),
+            VaultError::SecretNotFound(_) | VaultError::AttachmentNotFound(_) => {
+                CliError::NotFound(e.to_string())
+            }
+            VaultError::IoError(_) => CliError::Io(e.to_string()),
+            _ => CliError::Other(e.to_string()),
+        }
+    }
+}
+
+impl From<crate::utils::clipboard::ClipboardError> for CliError {
+    fn from(e: crate::utils::clipboard::ClipboardError) -> Self {
+        CliError::Other(e.to_string())
+    }
+}
+
+impl From<crate::crypto::shamir::ShamirError> for CliError {
+    fn from(e: crate::crypto::shamir::ShamirError) -> Self {
+        CliError::Other(e.to_string())
+    }
+}
+
+impl From<crate::vault::schema::SchemaError> for CliError {
+    fn from(e: crate::vault::schema::SchemaError) -> Self {
+        match e {
+            crate::vault::schema::SchemaError::NotFound(..) => CliError::NotFound(e.to_string()),
+            _ => CliError::Other(e.to_string()),
+        }
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(s: &str) -> Self {
+        CliError::Other(s.to_string())
+    }
+}
+
+impl From<String> for CliError {
+    fn from(s: String) -> Self {
+        CliError::Other(s)
+    }
+}
@@ -0,0 +1,58 @@
+//! Attach a file to a secret
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::session::cache;
+use crate::utils::{display, input};
+use crate::vault::VaultError;
+use std::fs;
+use std::path::Path;
+
+/// Fraction of `max_total_attachment_bytes` at which we warn and ask for
+/// confirmation before attaching, rather than rejecting outright.
+const WARN_THRESHOLD_RATIO: f64 = 0.8;
+
+/// Runs the attach command
+pub fn run(id_or_name: &str, file_path: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+
+    let bytes = fs::read(file_path)?;
+    let filename = Path::new(file_path)
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_path.to_string());
+
+    let max_total = cache::load_config().max_total_attachment_bytes;
+    let existing_total = vault.attachment_total_bytes(id_or_name).unwrap_or(0);
+    let new_total = existing_total + bytes.len() as u64;
+
+    if new_total > max_total {
+        return Err(VaultError::AttachmentTooLarge(format!(
+            "adding '{}' would bring total attachments on '{}' to {} bytes, exceeding the {} byte limit (see 'kookie config set max_total_attachment_bytes')",
+            filename, id_or_name, new_total, max_total
+        ))
+        .into());
+    }
+
+    if new_total as f64 > max_total as f64 * WARN_THRESHOLD_RATIO {
+        display::warning(&format!(
+            "Attaching '{}' brings total attachments on '{}' to {} of {} bytes.",
+            filename, id_or_name, new_total, max_total
+        ));
+        if !input::prompt_confirm("Continue?", true)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+
+    vault.attach(id_or_name, filename.clone(), &bytes)?;
+
+    display::success(&format!(
+        "Attached '{}' ({} bytes) to '{}'",
+        filename,
+        bytes.len(),
+        id_or_name
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! KDF benchmark command
+
+use crate::cli_error::CliError;
+use crate::crypto::kdf;
+use crate::utils::display;
+use colored::*;
+use std::time::{Duration, Instant};
+
+/// Number of derivations to average over
+const RUNS: usize = 5;
+
+/// Runs the bench-kdf command: derives a key `RUNS` times with the given
+/// Argon2id cost parameters and reports min/avg/max wall time. Read-only -
+/// doesn't touch the vault, just helps pick parameters before `init`.
+pub fn run(memory: Option<u32>, iterations: Option<u32>, parallelism: Option<u32>) -> Result<(), CliError> {
+    let memory_cost = memory.unwrap_or(65536);
+    let time_cost = iterations.unwrap_or(3);
+    let parallelism = parallelism.unwrap_or(4);
+
+    display::info(&format!(
+        "Benchmarking Argon2id (memory={} KB, iterations={}, parallelism={}), {} runs...",
+        memory_cost, time_cost, parallelism, RUNS
+    ));
+
+    let salt = kdf::generate_salt();
+    let mut durations = Vec::with_capacity(RUNS);
+
+    for _ in 0..RUNS {
+        let started = Instant::now();
+        if let Err(e) = kdf::derive_key_with_params("bench-kdf-probe", &salt, memory_cost, time_cost, parallelism) {
+            return Err(CliError::Other(format!("KDF derivation failed: {}", e)));
+        }
+        durations.push(started.elapsed());
+    }
+
+    let min = durations.iter().min().copied().unwrap_or(Duration::ZERO);
+    let max = durations.iter().max().copied().unwrap_or(Duration::ZERO);
+    let avg = durations.iter().sum::<Duration>() / durations.len() as u32;
+
+    println!();
+    println!("{} {:.0?}", "min:".dimmed(), min);
+    println!("{} {:.0?}", "avg:".dimmed(), avg);
+    println!("{} {:.0?}", "max:".dimmed(), max);
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! Mark/unmark a secret as burn-after-read
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+
+/// Marks a secret as burn-after-read: the next `kookie get` shows it once
+/// and then permanently deletes it.
+pub fn add(name_or_id: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    vault.set_burn_after_read(name_or_id, true)?;
+    display::success(&format!("'{}' marked as burn-after-read.", name_or_id));
+    Ok(())
+}
+
+/// Clears a secret's burn-after-read mark
+pub fn remove(name_or_id: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    vault.set_burn_after_read(name_or_id, false)?;
+    display::success(&format!("'{}' is no longer burn-after-read.", name_or_id));
+    Ok(())
+}
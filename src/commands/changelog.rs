@@ -0,0 +1,44 @@
+//! Changelog command: recent vault modifications
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use chrono::{Duration, Utc};
+
+/// Runs the changelog command, listing every secret created or modified
+/// within `since` of now, most recent first. Prints metadata only - no
+/// secret values.
+pub fn run(since_label: &str, since: Duration) -> Result<(), crate::cli_error::CliError> {
+    let vault = ensure_unlocked()?;
+    let cutoff = Utc::now() - since;
+
+    let mut entries: Vec<_> = vault
+        .data
+        .all_entries()
+        .into_iter()
+        .filter(|e| e.created_at >= cutoff || e.updated_at >= cutoff)
+        .collect();
+
+    if entries.is_empty() {
+        display::info("No changes in that window.");
+        return Ok(());
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.updated_at.max(e.created_at)));
+
+    println!();
+    display::info(&format!("Changes in the last {}:", since_label));
+    println!();
+
+    for entry in &entries {
+        let action = if entry.created_at >= cutoff { "created" } else { "modified" };
+        let extra = format!(
+            "{}, {}{}",
+            entry.secret_type,
+            action,
+            entry.extra.as_deref().map(|e| format!(", {}", e)).unwrap_or_default()
+        );
+        display::list_item(&entry.id, &entry.name, Some(&extra), entry.favorite, false);
+    }
+
+    Ok(())
+}
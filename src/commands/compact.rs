@@ -0,0 +1,69 @@
+//! Vault compaction and size reporting
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use crate::vault::VaultData;
+
+/// Runs the compact command
+///
+/// Re-saves the vault, which re-serializes `VaultData` as compact JSON
+/// (no pretty-printing) before re-encrypting it, and reports the
+/// `vault.json` size before and after. With `stats`, also breaks down the
+/// approximate plaintext bytes contributed by each secret type.
+pub fn run(stats: bool) -> Result<(), CliError> {
+    let vault = ensure_unlocked()?;
+
+    let before = std::fs::metadata(&vault.path)?.len();
+
+    if stats {
+        print_stats(&vault.data)?;
+    }
+
+    vault.save()?;
+
+    let after = std::fs::metadata(&vault.path)?.len();
+
+    println!();
+    display::info(&format!("vault.json size: {} bytes -> {} bytes", before, after));
+    if after < before {
+        display::success(&format!("Saved {} bytes.", before - after));
+    } else if after > before {
+        display::info(&format!("Grew by {} bytes.", after - before));
+    } else {
+        display::info("No change in size.");
+    }
+
+    Ok(())
+}
+
+/// Prints the secret count and approximate compact-JSON byte size
+/// contributed by each type in `data`, to help explain where vault.json's
+/// plaintext-before-encryption size is going.
+fn print_stats(data: &VaultData) -> Result<(), CliError> {
+    let rows = [
+        ("Passwords", data.passwords.len(), serde_json::to_vec(&data.passwords)?.len()),
+        ("API Keys", data.api_keys.len(), serde_json::to_vec(&data.api_keys)?.len()),
+        ("Notes", data.notes.len(), serde_json::to_vec(&data.notes)?.len()),
+        (
+            "Database Credentials",
+            data.db_credentials.len(),
+            serde_json::to_vec(&data.db_credentials)?.len(),
+        ),
+        ("Tokens", data.tokens.len(), serde_json::to_vec(&data.tokens)?.len()),
+        (
+            "Custom Secrets",
+            data.custom_secrets.len(),
+            serde_json::to_vec(&data.custom_secrets)?.len(),
+        ),
+        ("SSH Keys", data.ssh_keys.len(), serde_json::to_vec(&data.ssh_keys)?.len()),
+    ];
+
+    println!();
+    display::info("Plaintext size by secret type:");
+    for (label, count, bytes) in rows {
+        println!("  {:<22} {:>4} secret(s)  ~{} bytes", label, count, bytes);
+    }
+
+    Ok(())
+}
@@ -1,25 +1,93 @@
 //! Configuration command
 
-use crate::session::cache::{self, SessionConfig};
+use crate::session::cache;
 use crate::utils::display;
+use crate::utils::generators::PasswordPolicy;
+
+/// A `config set`/`config get`/`config add-policy` subcommand
+#[derive(Debug, Clone)]
+pub enum ConfigAction {
+    Set { key: String, value: String },
+    Get { key: String },
+    AddPolicy {
+        name: String,
+        min_len: usize,
+        require_symbol: bool,
+        require_digit: bool,
+        exclude: String,
+    },
+}
+
+/// The set of keys recognized by `config set`/`config get`, mapped onto
+/// `SessionConfig` fields.
+const VALID_KEYS: &[&str] = &[
+    "timeout_minutes",
+    "clipboard_history_protection",
+    "unlock_attempts",
+    "reveal_mode",
+    "max_total_attachment_bytes",
+    "trash_retention_days",
+    "storage_backend",
+    "clipboard_command",
+    "normalize_names",
+    "mask_char",
+    "git_autocommit",
+];
 
 /// Runs the config command
-pub fn run(timeout: Option<u32>, show: bool) -> Result<(), Box<dyn std::error::Error>> {
+pub fn run(
+    timeout: Option<u32>,
+    show: bool,
+    reset: bool,
+    action: Option<ConfigAction>,
+) -> Result<(), crate::cli_error::CliError> {
+    if reset {
+        if crate::utils::input::prompt_confirm(
+            "Reset every config setting to its default?",
+            false,
+        )? {
+            cache::save_config(&cache::SessionConfig::default())?;
+            display::success("Configuration reset to defaults.");
+        } else {
+            display::info("Aborted.");
+        }
+        return Ok(());
+    }
+
+    if let Some(action) = action {
+        return match action {
+            ConfigAction::Set { key, value } => set_key(&key, &value),
+            ConfigAction::Get { key } => get_key(&key),
+            ConfigAction::AddPolicy { name, min_len, require_symbol, require_digit, exclude } => {
+                add_policy(name, min_len, require_symbol, require_digit, exclude)
+            }
+        };
+    }
+
     if show {
         let config = cache::load_config();
         println!();
         display::info("Current configuration:");
-        println!("  Unlock timeout: {} minutes", config.timeout_minutes);
+        println!("  timeout_minutes: {}", config.timeout_minutes);
+        println!("  clipboard_history_protection: {}", config.clipboard_history_protection);
+        println!("  unlock_attempts: {}", config.unlock_attempts);
+        println!("  reveal_mode: {}", config.reveal_mode);
+        println!("  max_total_attachment_bytes: {}", config.max_total_attachment_bytes);
+        println!("  trash_retention_days: {}", config.trash_retention_days);
+        println!("  storage_backend: {}", config.storage_backend);
+        println!("  clipboard_command: {}", config.clipboard_command.as_deref().unwrap_or("(not set, uses native clipboard)"));
+        println!("  normalize_names: {}", config.normalize_names);
+        println!("  mask_char: {}", config.mask_char);
+        println!("  git_autocommit: {}", config.git_autocommit);
         println!();
         return Ok(());
     }
-    
+
     if let Some(minutes) = timeout {
-        let config = SessionConfig {
-            timeout_minutes: minutes,
-        };
+        let mut config = cache::load_config();
+        config.timeout_minutes = minutes;
         cache::save_config(&config)?;
-        
+
         if minutes == 0 {
             display::success("Timeout disabled. Password will be required for every operation.");
         } else {
@@ -31,7 +99,221 @@ pub fn run(timeout: Option<u32>, show: bool) -> Result<(), Box<dyn std::error::E
     } else {
         display::info("Usage: kookie config --timeout <minutes>");
         display::info("       kookie config --show");
+        display::info("       kookie config --reset");
+        display::info("       kookie config set <key> <value>");
+        display::info("       kookie config get <key>");
+    }
+
+    Ok(())
+}
+
+fn set_key(key: &str, value: &str) -> Result<(), crate::cli_error::CliError> {
+    let mut config = cache::load_config();
+    match key {
+        "timeout_minutes" => {
+            let minutes: u32 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid number of minutes", value))?;
+            config.timeout_minutes = minutes;
+            cache::save_config(&config)?;
+            display::success(&format!("timeout_minutes set to {}.", minutes));
+            Ok(())
+        }
+        "clipboard_history_protection" => {
+            let enabled: bool = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid boolean (use true/false)", value))?;
+            config.clipboard_history_protection = enabled;
+            cache::save_config(&config)?;
+            display::success(&format!("clipboard_history_protection set to {}.", enabled));
+            Ok(())
+        }
+        "unlock_attempts" => {
+            let attempts: u32 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid number of attempts", value))?;
+            if attempts == 0 {
+                return Err(crate::cli_error::CliError::Other(
+                    "unlock_attempts must be at least 1".to_string(),
+                ));
+            }
+            config.unlock_attempts = attempts;
+            cache::save_config(&config)?;
+            display::success(&format!("unlock_attempts set to {}.", attempts));
+            Ok(())
+        }
+        "reveal_mode" => {
+            let mode: crate::utils::display::RevealMode = value
+                .parse()
+                .map_err(crate::cli_error::CliError::Other)?;
+            config.reveal_mode = mode;
+            cache::save_config(&config)?;
+            display::success(&format!("reveal_mode set to {}.", mode));
+            Ok(())
+        }
+        "max_total_attachment_bytes" => {
+            let bytes: u64 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid number of bytes", value))?;
+            if bytes == 0 {
+                return Err(crate::cli_error::CliError::Other(
+                    "max_total_attachment_bytes must be at least 1".to_string(),
+                ));
+            }
+            config.max_total_attachment_bytes = bytes;
+            cache::save_config(&config)?;
+            display::success(&format!("max_total_attachment_bytes set to {}.", bytes));
+            Ok(())
+        }
+        "trash_retention_days" => {
+            let days: u32 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid number of days", value))?;
+            config.trash_retention_days = days;
+            cache::save_config(&config)?;
+            display::success(&format!("trash_retention_days set to {}.", days));
+            Ok(())
+        }
+        "storage_backend" => {
+            let backend: crate::vault::storage::StorageBackend =
+                value.parse().map_err(crate::cli_error::CliError::Other)?;
+            config.storage_backend = backend;
+            cache::save_config(&config)?;
+            display::success(&format!("storage_backend set to {}.", backend));
+            Ok(())
+        }
+        "clipboard_command" => {
+            if value.is_empty() {
+                config.clipboard_command = None;
+                cache::save_config(&config)?;
+                display::success("clipboard_command cleared; using the native clipboard.");
+            } else {
+                config.clipboard_command = Some(value.to_string());
+                cache::save_config(&config)?;
+                display::success(&format!("clipboard_command set to '{}'.", value));
+            }
+            Ok(())
+        }
+        "normalize_names" => {
+            let enabled: bool = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid boolean (use true/false)", value))?;
+            config.normalize_names = enabled;
+            cache::save_config(&config)?;
+            display::success(&format!("normalize_names set to {}.", enabled));
+            Ok(())
+        }
+        "mask_char" => {
+            let mut chars = value.chars();
+            let mask_char = chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .ok_or_else(|| format!("'{}' is not a single character", value))?;
+            config.mask_char = mask_char;
+            cache::save_config(&config)?;
+            display::success(&format!("mask_char set to '{}'.", mask_char));
+            Ok(())
+        }
+        "git_autocommit" => {
+            let enabled: bool = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a valid boolean (use true/false)", value))?;
+            config.git_autocommit = enabled;
+            cache::save_config(&config)?;
+            display::success(&format!("git_autocommit set to {}.", enabled));
+            if enabled {
+                display::info("Run 'kookie git init' if you haven't already, or this has no effect.");
+            }
+            Ok(())
+        }
+        other => Err(crate::cli_error::CliError::Other(format!(
+            "Unknown config key '{}'. Valid keys: {}",
+            other,
+            VALID_KEYS.join(", ")
+        ))),
     }
-    
+}
+
+fn add_policy(
+    name: String,
+    min_len: usize,
+    require_symbol: bool,
+    require_digit: bool,
+    exclude: String,
+) -> Result<(), crate::cli_error::CliError> {
+    if PasswordPolicy::built_in(&name).is_some() {
+        display::warning(&format!("'{}' is a built-in policy name; this overrides it.", name));
+    }
+
+    let mut config = cache::load_config();
+    config.policies.insert(
+        name.clone(),
+        PasswordPolicy {
+            min_len,
+            require_symbol,
+            require_digit,
+            exclude,
+        },
+    );
+    cache::save_config(&config)?;
+    display::success(&format!(
+        "Policy '{}' saved. Use it with 'kookie generate password --policy {}'.",
+        name, name
+    ));
     Ok(())
 }
+
+fn get_key(key: &str) -> Result<(), crate::cli_error::CliError> {
+    let config = cache::load_config();
+    match key {
+        "timeout_minutes" => {
+            println!("{}", config.timeout_minutes);
+            Ok(())
+        }
+        "clipboard_history_protection" => {
+            println!("{}", config.clipboard_history_protection);
+            Ok(())
+        }
+        "unlock_attempts" => {
+            println!("{}", config.unlock_attempts);
+            Ok(())
+        }
+        "reveal_mode" => {
+            println!("{}", config.reveal_mode);
+            Ok(())
+        }
+        "max_total_attachment_bytes" => {
+            println!("{}", config.max_total_attachment_bytes);
+            Ok(())
+        }
+        "trash_retention_days" => {
+            println!("{}", config.trash_retention_days);
+            Ok(())
+        }
+        "storage_backend" => {
+            println!("{}", config.storage_backend);
+            Ok(())
+        }
+        "clipboard_command" => {
+            println!("{}", config.clipboard_command.as_deref().unwrap_or(""));
+            Ok(())
+        }
+        "normalize_names" => {
+            println!("{}", config.normalize_names);
+            Ok(())
+        }
+        "mask_char" => {
+            println!("{}", config.mask_char);
+            Ok(())
+        }
+        "git_autocommit" => {
+            println!("{}", config.git_autocommit);
+            Ok(())
+        }
+        other => Err(crate::cli_error::CliError::Other(format!(
+            "Unknown config key '{}'. Valid keys: {}",
+            other,
+            VALID_KEYS.join(", ")
+        ))),
+    }
+}
@@ -0,0 +1,61 @@
+//! Copy secret command
+//!
+//! A focused alternative to `kookie get --copy`: copies a secret's default
+//! value straight to the clipboard and prints nothing but a confirmation,
+//! never rendering the value to the terminal. Meant for screen-sharing
+//! contexts where even a masked `kookie get` is too risky to run on camera.
+
+use crate::cli_error::CliError;
+use crate::commands::get::{burn_if_needed, copy_or_warn, format_password_copy, resolve_id_or_name};
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+
+/// Runs the copy command. Shares `get::run`'s matching logic (id/name/prefix
+/// resolution, burn-after-read handling) but never displays the value -
+/// only `copy_or_warn`'s confirmation message reaches the terminal.
+pub fn run(id_or_name: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+
+    let resolved = resolve_id_or_name(&vault, id_or_name)?;
+    let id_or_name = resolved.as_str();
+
+    if let Some(p) = vault.get_password(id_or_name).cloned() {
+        let result = copy_or_warn(&format_password_copy(&p, Default::default()), "Password copied to clipboard!");
+        return burn_if_needed(p.burn_after_read, result, || vault.delete_password(id_or_name));
+    }
+
+    if let Some(k) = vault.get_api_key(id_or_name).cloned() {
+        let result = copy_or_warn(&k.key, "API key copied to clipboard!");
+        return burn_if_needed(k.burn_after_read, result, || vault.delete_api_key(id_or_name));
+    }
+
+    if let Some(n) = vault.get_note(id_or_name).cloned() {
+        let result = copy_or_warn(&n.content, "Note content copied to clipboard!");
+        return burn_if_needed(n.burn_after_read, result, || vault.delete_note(id_or_name));
+    }
+
+    if let Some(c) = vault.get_db_credential(id_or_name).cloned() {
+        let result = copy_or_warn(&c.connection_string(), "Connection string copied to clipboard!");
+        return burn_if_needed(c.burn_after_read, result, || vault.delete_db_credential(id_or_name));
+    }
+
+    if let Some(t) = vault.get_token(id_or_name).cloned() {
+        let result = copy_or_warn(&t.token, "Token copied to clipboard!");
+        return burn_if_needed(t.burn_after_read, result, || vault.delete_token(id_or_name));
+    }
+
+    if vault.get_custom(id_or_name).is_some() {
+        display::warning("Custom secrets have multiple fields; use 'kookie get --field <name>' instead");
+        return Ok(());
+    }
+
+    if let Some(s) = vault.get_ssh_key(id_or_name).cloned() {
+        let result = copy_or_warn(&s.private_key, "Private key copied to clipboard!");
+        return burn_if_needed(s.burn_after_read, result, || vault.delete_ssh_key(id_or_name));
+    }
+
+    Err(CliError::NotFound(format!(
+        "Secret '{}' not found. Use 'kookie list' to see all secrets.",
+        id_or_name
+    )))
+}
@@ -1,12 +1,24 @@
 //! Delete secret command
 
+use crate::cli_error::CliError;
+use crate::commands::get::resolve_id_or_name;
 use crate::commands::lock::ensure_unlocked;
 use crate::utils::{display, input};
 
-/// Runs the delete command
-pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+/// Runs the delete command. By default this moves the secret to the trash
+/// (see `commands::trash`); `permanent` bypasses the trash and removes it
+/// outright, and requires `force` so it can't be triggered by accident.
+pub fn run(id_or_name: &str, force: bool, permanent: bool) -> Result<(), CliError> {
+    if permanent && !force {
+        return Err(CliError::Other(
+            "--permanent requires --force, to avoid skipping the trash by accident.".to_string(),
+        ));
+    }
+
     let mut vault = ensure_unlocked()?;
-    
+    let resolved = resolve_id_or_name(&vault, id_or_name)?;
+    let id_or_name = resolved.as_str();
+
     // Check if secret exists
     let secret_type = if vault.get_password(id_or_name).is_some() {
         Some("password")
@@ -18,18 +30,19 @@ pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Erro
         Some("database credential")
     } else if vault.get_token(id_or_name).is_some() {
         Some("token")
+    } else if vault.get_custom(id_or_name).is_some() {
+        Some("custom secret")
+    } else if vault.get_ssh_key(id_or_name).is_some() {
+        Some("SSH key")
     } else {
         None
     };
-    
+
     let secret_type = match secret_type {
         Some(t) => t,
-        None => {
-            display::error(&format!("Secret '{}' not found.", id_or_name));
-            return Ok(());
-        }
+        None => return Err(CliError::NotFound(format!("Secret '{}' not found.", id_or_name))),
     };
-    
+
     // Confirm deletion
     if !force {
         display::warning(&format!(
@@ -41,23 +54,33 @@ pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Erro
             return Ok(());
         }
     }
-    
-    // Delete based on type
-    let deleted_name = if vault.get_password(id_or_name).is_some() {
-        vault.delete_password(id_or_name)?.name
-    } else if vault.get_api_key(id_or_name).is_some() {
-        vault.delete_api_key(id_or_name)?.name
-    } else if vault.get_note(id_or_name).is_some() {
-        vault.delete_note(id_or_name)?.name
-    } else if vault.get_db_credential(id_or_name).is_some() {
-        vault.delete_db_credential(id_or_name)?.name
-    } else if vault.get_token(id_or_name).is_some() {
-        vault.delete_token(id_or_name)?.name
+
+    if permanent {
+        let deleted_name = if vault.get_password(id_or_name).is_some() {
+            vault.delete_password(id_or_name)?.name
+        } else if vault.get_api_key(id_or_name).is_some() {
+            vault.delete_api_key(id_or_name)?.name
+        } else if vault.get_note(id_or_name).is_some() {
+            vault.delete_note(id_or_name)?.name
+        } else if vault.get_db_credential(id_or_name).is_some() {
+            vault.delete_db_credential(id_or_name)?.name
+        } else if vault.get_token(id_or_name).is_some() {
+            vault.delete_token(id_or_name)?.name
+        } else if vault.get_custom(id_or_name).is_some() {
+            vault.delete_custom(id_or_name)?.name
+        } else if vault.get_ssh_key(id_or_name).is_some() {
+            vault.delete_ssh_key(id_or_name)?.name
+        } else {
+            return Ok(());
+        };
+        display::success(&format!("Permanently deleted {} '{}'", secret_type, deleted_name));
     } else {
-        return Ok(());
-    };
-    
-    display::success(&format!("Deleted {} '{}'", secret_type, deleted_name));
-    
+        let trashed_name = vault.trash(id_or_name)?;
+        display::success(&format!(
+            "Moved {} '{}' to the trash. Use 'kookie restore {}' to undo.",
+            secret_type, trashed_name, trashed_name
+        ));
+    }
+
     Ok(())
 }
@@ -0,0 +1,175 @@
+//! Environment diagnostics command
+
+use crate::cli_error::CliError;
+use crate::session::cache;
+use crate::utils::{clipboard, display};
+use crate::vault::Vault;
+
+/// Runs the doctor command, printing a pass/warn/fail checklist covering
+/// the usual sources of support requests: vault existence/permissions,
+/// the resolved vault directory, whether the install location is on PATH,
+/// clipboard availability, config validity, session state, and KDF timing
+/// on this machine. Never unlocks the vault. Exits non-zero (via
+/// `CliError::Other`) if any check came back `fail` rather than `warn` or
+/// `info` - a `fail` means kookie itself is broken on this machine, not
+/// just that setup is incomplete.
+pub fn run() -> Result<(), CliError> {
+    println!();
+    display::info("Running kookie diagnostics...");
+    println!();
+
+    check_vault_dir();
+    let mut critical = check_vault();
+    critical |= check_file_permissions();
+    check_install_path();
+    check_clipboard();
+    critical |= check_config();
+    check_session();
+    critical |= check_kdf_timing();
+
+    println!();
+
+    if critical {
+        return Err(CliError::Other(
+            "One or more critical checks failed; see 'fail' lines above.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_vault_dir() {
+    display::info(&format!("Vault directory: {}", crate::vault::storage::get_vault_dir().display()));
+}
+
+/// Returns whether the vault file itself is unreadable (a `fail`) - a
+/// missing vault is just an unfinished setup (`warn`), not broken.
+fn check_vault() -> bool {
+    let vault = Vault::new();
+    if !vault.exists() {
+        display::warning(&format!("Vault: not initialized ({})", vault.path.display()));
+        return false;
+    }
+    display::success(&format!("Vault: found at {}", vault.path.display()));
+    false
+}
+
+/// Reports the mode of every vault-related file that might exist (the
+/// vault itself, the session cache, and the config), flagging any that are
+/// group/other-readable and offering to tighten them to `0600` on the spot
+/// - the same fix `load_vault_file` applies automatically, pulled into one
+///   place so a user can audit and repair everything at once.
+#[cfg(unix)]
+fn check_file_permissions() -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    let paths = [
+        ("Vault", crate::vault::storage::get_vault_path()),
+        ("Session", crate::vault::storage::get_session_path()),
+        ("Config", crate::vault::storage::get_config_path()),
+    ];
+
+    let mut critical = false;
+
+    for (label, path) in paths {
+        if !path.exists() {
+            continue;
+        }
+        match std::fs::metadata(&path) {
+            Ok(metadata) => {
+                let mode = metadata.permissions().mode() & 0o777;
+                if mode & 0o077 != 0 {
+                    if let Err(e) = crate::vault::storage::check_permissions(&path) {
+                        display::fail(&format!("{} permissions: failed to fix ({})", label, e));
+                        critical = true;
+                    }
+                } else {
+                    display::success(&format!("{} permissions: {:o}", label, mode));
+                }
+            }
+            Err(e) => {
+                display::fail(&format!("{} permissions: could not read ({})", label, e));
+                critical = true;
+            }
+        }
+    }
+
+    critical
+}
+
+#[cfg(not(unix))]
+fn check_file_permissions() -> bool {
+    false
+}
+
+fn check_install_path() {
+    if crate::commands::install::is_install_dir_on_path() {
+        display::success("Install location: on PATH");
+    } else {
+        display::warning("Install location: not on PATH; run 'kookie install'");
+    }
+}
+
+fn check_clipboard() {
+    if clipboard::is_available() {
+        display::success("Clipboard: backend available");
+    } else {
+        display::warning("Clipboard: no backend available (use --show instead of --copy)");
+    }
+}
+
+fn check_config() -> bool {
+    let path = crate::vault::storage::get_config_path();
+    if !path.exists() {
+        display::info("Config: using defaults (no config.json yet)");
+        return false;
+    }
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str::<cache::SessionConfig>(&content) {
+            Ok(_) => {
+                display::success(&format!("Config: valid ({})", path.display()));
+                false
+            }
+            Err(e) => {
+                display::fail(&format!("Config: invalid JSON at {} ({})", path.display(), e));
+                true
+            }
+        },
+        Err(e) => {
+            display::fail(&format!("Config: could not read {} ({})", path.display(), e));
+            true
+        }
+    }
+}
+
+fn check_session() {
+    if cache::is_locked() {
+        display::info("Session: explicitly locked");
+    } else if let Some(mins) = cache::session_remaining_minutes() {
+        display::success(&format!("Session: active, {} minute(s) remaining", mins));
+    } else {
+        display::info("Session: none (master password required)");
+    }
+}
+
+fn check_kdf_timing() -> bool {
+    let salt = crate::crypto::kdf::generate_salt();
+    let started = std::time::Instant::now();
+    let result = display::with_spinner("Timing KDF...", || {
+        crate::crypto::kdf::derive_key_with_profile(
+            "doctor-probe",
+            &salt,
+            crate::crypto::kdf::KdfProfile::Standard,
+        )
+    });
+    match result {
+        Ok(_) => {
+            display::success(&format!("KDF (standard): {:.0?}", started.elapsed()));
+            false
+        }
+        Err(e) => {
+            display::fail(&format!("KDF (standard): failed ({})", e));
+            true
+        }
+    }
+}
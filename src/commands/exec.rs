@@ -0,0 +1,95 @@
+//! Run a subprocess with secrets injected into its environment
+//!
+//! Inspired by tools like `envchain`: secrets never touch disk or the
+//! parent shell's environment, only the child process's.
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::vault::Vault;
+use std::process::Command;
+
+/// Uppercases `name` and replaces anything that isn't `[A-Za-z0-9_]` with
+/// `_`, so an arbitrary secret name becomes a usable environment variable
+/// name (e.g. "stripe-api-key" -> "STRIPE_API_KEY").
+fn env_var_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// Returns a secret's primary value by name, searching every secret type
+/// the same way `kookie get --export` does.
+fn secret_value(vault: &Vault, id_or_name: &str) -> Option<String> {
+    if let Some(p) = vault.get_password(id_or_name) {
+        return Some(p.password.clone());
+    }
+    if let Some(k) = vault.get_api_key(id_or_name) {
+        return Some(k.key.clone());
+    }
+    if let Some(n) = vault.get_note(id_or_name) {
+        return Some(n.content.clone());
+    }
+    if let Some(c) = vault.get_db_credential(id_or_name) {
+        return Some(c.connection_string());
+    }
+    if let Some(t) = vault.get_token(id_or_name) {
+        return Some(t.token.clone());
+    }
+    if let Some(s) = vault.get_ssh_key(id_or_name) {
+        return Some(s.private_key.clone());
+    }
+    None
+}
+
+/// Parses a `NAME=secret-name` mapping from `--env`.
+fn parse_env_mapping(mapping: &str) -> Result<(String, String), CliError> {
+    match mapping.split_once('=') {
+        Some((name, secret_name)) if !name.is_empty() && !secret_name.is_empty() => {
+            Ok((name.to_string(), secret_name.to_string()))
+        }
+        _ => Err(CliError::Other(format!(
+            "Invalid --env mapping '{}'. Expected NAME=secret-name.",
+            mapping
+        ))),
+    }
+}
+
+/// Runs the exec command: unlocks the vault, resolves secrets into
+/// environment variables (by `--tag` and/or `--env NAME=secret-name`), and
+/// runs `command` with them set, without ever writing them to disk or
+/// leaking them into the parent shell's own environment.
+pub fn run(tag: Option<String>, env_mappings: Vec<String>, command: Vec<String>) -> Result<(), CliError> {
+    let (program, args) = command.split_first().ok_or_else(|| {
+        CliError::Other("No command given. Usage: kookie exec [OPTIONS] -- <command> [args...]".to_string())
+    })?;
+
+    let vault = ensure_unlocked()?;
+
+    let mut env: Vec<(String, String)> = Vec::new();
+
+    if let Some(tag) = &tag {
+        for k in vault.data.api_keys.iter().filter(|k| k.tags.iter().any(|t| t == tag)) {
+            env.push((env_var_name(&k.name), k.key.clone()));
+        }
+        for t in vault.data.tokens.iter().filter(|t| t.tags.iter().any(|x| x == tag)) {
+            env.push((env_var_name(&t.name), t.token.clone()));
+        }
+    }
+
+    for mapping in &env_mappings {
+        let (name, secret_name) = parse_env_mapping(mapping)?;
+        let value = secret_value(&vault, &secret_name)
+            .ok_or_else(|| CliError::NotFound(format!("Secret '{}' not found.", secret_name)))?;
+        env.push((name, value));
+    }
+
+    if env.is_empty() {
+        return Err(CliError::Other(
+            "No secrets selected. Pass --tag <tag> and/or --env NAME=secret-name.".to_string(),
+        ));
+    }
+
+    let status = Command::new(program).args(args).envs(env).status()?;
+
+    std::process::exit(status.code().unwrap_or(1));
+}
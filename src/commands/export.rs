@@ -0,0 +1,91 @@
+//! Export the current vault's decrypted data to an age-encrypted file, for
+//! offsite backups that don't depend on kookie's own KDF/cipher or the
+//! master password - only on holding the matching age identity.
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use std::path::Path;
+
+/// Runs the export command: decrypts the vault, serializes it to the same
+/// `VaultData` JSON `import` reads, and re-encrypts it to an age recipient.
+///
+/// Requires the `age` feature; without it this returns a clear error
+/// telling the user to rebuild with it rather than silently no-op'ing.
+pub fn run(recipient: &str, out: &Path) -> Result<(), CliError> {
+    let vault = ensure_unlocked()?;
+    let plaintext = serde_json::to_vec(&vault.data)?;
+
+    age_backend::encrypt(recipient, &plaintext, out)?;
+
+    display::success(&format!("Exported vault to {} (age-encrypted).", out.display()));
+    Ok(())
+}
+
+#[cfg(feature = "age")]
+pub(crate) mod age_backend {
+    use crate::cli_error::CliError;
+    use std::io::Write;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    pub fn encrypt(recipient: &str, plaintext: &[u8], out: &Path) -> Result<(), CliError> {
+        let recipient = age::x25519::Recipient::from_str(recipient)
+            .map_err(|e| CliError::Other(format!("Invalid age recipient: {}", e)))?;
+
+        let encryptor = age::Encryptor::with_recipients(vec![Box::new(recipient)])
+            .ok_or_else(|| CliError::Other("Failed to build age encryptor".to_string()))?;
+
+        let mut encrypted = Vec::new();
+        let mut writer = encryptor
+            .wrap_output(&mut encrypted)
+            .map_err(|e| CliError::Other(format!("age encryption failed: {}", e)))?;
+        writer
+            .write_all(plaintext)
+            .map_err(|e| CliError::Other(format!("age encryption failed: {}", e)))?;
+        writer
+            .finish()
+            .map_err(|e| CliError::Other(format!("age encryption failed: {}", e)))?;
+
+        crate::utils::secure_fs::write(out, &encrypted)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "age"))]
+pub(crate) mod age_backend {
+    use crate::cli_error::CliError;
+    use std::path::Path;
+
+    pub fn encrypt(_recipient: &str, _plaintext: &[u8], _out: &Path) -> Result<(), CliError> {
+        Err(CliError::Other(
+            "Age-encrypted export requires the 'age' feature; rebuild with `--features age`.".to_string(),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "age"))]
+mod tests {
+    use super::age_backend;
+    use crate::commands::import::age_backend as import_age_backend;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let identity = age::x25519::Identity::generate();
+        let recipient = identity.to_public().to_string();
+
+        let dir = tempfile::tempdir().unwrap();
+        let encrypted_path = dir.path().join("backup.age");
+        let identity_path = dir.path().join("identity.txt");
+        std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+
+        age_backend::encrypt(&recipient, b"{\"passwords\":[]}", &encrypted_path).unwrap();
+
+        let encrypted = std::fs::read(&encrypted_path).unwrap();
+        let decrypted = import_age_backend::decrypt(&identity_path, &encrypted).unwrap();
+
+        assert_eq!(decrypted, b"{\"passwords\":[]}");
+    }
+}
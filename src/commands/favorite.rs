@@ -0,0 +1,21 @@
+//! Favorite/unfavorite a secret command
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+
+/// Marks a secret as a favorite, so it sorts first in `kookie list`
+pub fn add(name_or_id: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    vault.set_favorite(name_or_id, true)?;
+    display::success(&format!("'{}' marked as a favorite.", name_or_id));
+    Ok(())
+}
+
+/// Removes a secret's favorite mark
+pub fn remove(name_or_id: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    vault.set_favorite(name_or_id, false)?;
+    display::success(&format!("'{}' removed from favorites.", name_or_id));
+    Ok(())
+}
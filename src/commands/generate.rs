@@ -1,6 +1,13 @@
 //! Generate secrets command
 
-use crate::utils::{clipboard, display, generators};
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::session::cache;
+use crate::utils::generators::{KeyEncoding, PasswordPolicy, UuidVersion};
+use crate::utils::{clipboard, display, generators, input};
+use crate::vault::types::{ApiKey, Password, SshKey, Token};
+use ssh_key::{Algorithm, LineEnding, PrivateKey};
+use std::path::{Path, PathBuf};
 
 /// Type of key to generate
 #[derive(Debug, Clone, Copy)]
@@ -9,39 +16,299 @@ pub enum GenerateType {
     Key,
     Password,
     ApiKey,
+    Uuid(UuidVersion),
 }
 
-/// Runs the generate command
-pub fn run(gen_type: GenerateType, length: Option<usize>, copy: bool, symbols: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let (label, value) = match gen_type {
+/// Default number of random bytes in a generated API key's random portion
+const DEFAULT_API_KEY_LENGTH: usize = 24;
+
+/// Default prefix for a generated API key
+const DEFAULT_API_KEY_PREFIX: &str = "kk_";
+
+/// Resolves a `--policy` name to a policy: checks the built-ins first
+/// (`strong`, `pin`, `alnum`), then custom policies defined via
+/// `kookie config add-policy`.
+fn resolve_policy(name: &str) -> Result<PasswordPolicy, crate::cli_error::CliError> {
+    if let Some(policy) = PasswordPolicy::built_in(name) {
+        return Ok(policy);
+    }
+    cache::load_config().policies.get(name).cloned().ok_or_else(|| {
+        crate::cli_error::CliError::NotFound(format!(
+            "Unknown policy '{}'. Built-ins: strong, pin, alnum. Define custom ones with \
+             'kookie config add-policy'.",
+            name
+        ))
+    })
+}
+
+/// Produces one generated value and its display label
+#[allow(clippy::too_many_arguments)]
+fn generate_one(
+    gen_type: GenerateType,
+    length: Option<usize>,
+    symbols: bool,
+    encoding: Option<KeyEncoding>,
+    prefix: Option<&str>,
+    policy: Option<&PasswordPolicy>,
+) -> (String, String) {
+    match gen_type {
         GenerateType::Jwt => {
             let secret = generators::generate_jwt_secret();
             ("JWT Secret (256-bit)".to_string(), secret)
         }
         GenerateType::Key => {
             let len = length.unwrap_or(32);
-            let key = generators::generate_random_key(len);
-            (format!("Random Key ({} bytes)", len), key)
+            let encoding = encoding.unwrap_or_default();
+            let key = generators::generate_random_key(len, encoding);
+            (format!("Random Key ({} bytes, {:?})", len, encoding), key)
+        }
+        GenerateType::Password => match policy {
+            Some(policy) => {
+                let password = generators::generate_password_with_policy(length, policy);
+                (format!("Random Password ({} chars, policy)", password.len()), password)
+            }
+            None => {
+                let len = length.unwrap_or(16);
+                let password = generators::generate_password(len, symbols);
+                (format!("Random Password ({} chars)", len), password)
+            }
+        },
+        GenerateType::ApiKey => {
+            let len = length.unwrap_or(DEFAULT_API_KEY_LENGTH);
+            let prefix = prefix.unwrap_or(DEFAULT_API_KEY_PREFIX);
+            let key = generators::generate_api_key(prefix, len);
+            (format!("API Key ({} bytes, prefix \"{}\")", len, prefix), key)
+        }
+        GenerateType::Uuid(version) => {
+            let id = generators::generate_uuid(version);
+            (format!("UUID ({:?})", version), id)
         }
+    }
+}
+
+/// Writes `content` to `path` with no trailing newline, refusing to
+/// overwrite an existing file unless `force` is set. On Unix the file is
+/// created with `0o600` permissions, since generated secrets are as
+/// sensitive as anything already stored in the vault. Returns the number
+/// of bytes written.
+fn write_out(path: &Path, content: &str, force: bool) -> Result<usize, CliError> {
+    if path.exists() && !force {
+        return Err(CliError::Other(format!(
+            "{} already exists; use --force to overwrite",
+            path.display()
+        )));
+    }
+
+    crate::utils::secure_fs::write(path, content.as_bytes())?;
+
+    Ok(content.len())
+}
+
+/// Runs the generate command
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    gen_type: GenerateType,
+    length: Option<usize>,
+    copy: bool,
+    symbols: bool,
+    encoding: Option<KeyEncoding>,
+    count: Option<usize>,
+    prefix: Option<String>,
+    policy: Option<String>,
+    save: bool,
+    name: Option<String>,
+    no_prompt: bool,
+    out: Option<PathBuf>,
+    force: bool,
+) -> Result<(), CliError> {
+    let count = count.unwrap_or(1);
+    let prefix = prefix.as_deref();
+    let policy = policy.map(|name| resolve_policy(&name)).transpose()?;
+
+    if count == 0 {
+        return Err(CliError::Other("--count must be at least 1".to_string()));
+    }
+
+    if copy && count > 1 {
+        return Err(CliError::Other(
+            "--copy can't be used with --count > 1 (ambiguous which value to copy)".to_string(),
+        ));
+    }
+
+    if save && count > 1 {
+        return Err(CliError::Other(
+            "--save can't be used with --count > 1 (ambiguous which value to store)".to_string(),
+        ));
+    }
+
+    if save && name.is_none() {
+        return Err(CliError::Other("--save requires --name".to_string()));
+    }
+
+    if count > 1 {
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (_, value) = generate_one(gen_type, length, symbols, encoding, prefix, policy.as_ref());
+            values.push(value);
+        }
+        match out {
+            Some(path) => {
+                let n = write_out(&path, &values.join("\n"), force)?;
+                display::success(&format!("Wrote {} bytes to {}.", n, path.display()));
+            }
+            // Bulk mode: plain values, one per line, suitable for scripting.
+            None => {
+                for value in &values {
+                    println!("{}", value);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let (label, value) = generate_one(gen_type, length, symbols, encoding, prefix, policy.as_ref());
+
+    match &out {
+        Some(path) => {
+            let n = write_out(path, &value, force)?;
+            display::success(&format!("Wrote {} bytes to {}.", n, path.display()));
+        }
+        None => {
+            println!();
+            println!("{}: {}", label, value);
+            println!();
+        }
+    }
+
+    if copy {
+        match clipboard::copy_to_clipboard(&value, true) {
+            Ok(()) => display::success("Copied to clipboard!"),
+            Err(clipboard::ClipboardError::Unavailable) => {
+                display::warning("No clipboard available; use --show to print the value")
+            }
+            Err(clipboard::ClipboardError::VerificationMismatch) => {
+                display::warning("Clipboard copy couldn't be verified (read-back didn't match); try again or print the value")
+            }
+            Err(clipboard::ClipboardError::VerificationUnsupported) => {
+                display::success("Copied to clipboard!")
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if save {
+        save_generated(gen_type, name.expect("checked above"), value, !no_prompt)?;
+    }
+
+    Ok(())
+}
+
+/// Unlocks the vault and stores a freshly generated value as the secret
+/// type matching `gen_type`, named `name`. `prompt_details` controls
+/// whether optional per-type fields (currently username/url for passwords)
+/// are additionally prompted for, so `--no-prompt` can skip straight to a
+/// non-interactive save.
+fn save_generated(
+    gen_type: GenerateType,
+    name: String,
+    value: String,
+    prompt_details: bool,
+) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+
+    match gen_type {
         GenerateType::Password => {
-            let len = length.unwrap_or(16);
-            let password = generators::generate_password(len, symbols);
-            (format!("Random Password ({} chars)", len), password)
+            let (username, url) = if prompt_details {
+                (input::prompt_optional("Username (optional):")?, input::prompt_optional("URL (optional):")?)
+            } else {
+                (None, None)
+            };
+            vault.add_password(Password::new(name.clone(), value, None, username, url, None, None, None)?)?;
         }
         GenerateType::ApiKey => {
-            let key = generators::generate_api_key();
-            ("API Key".to_string(), key)
+            vault.add_api_key(ApiKey::new(name.clone(), value, None, None, None, Vec::new(), None)?)?;
+        }
+        GenerateType::Jwt => {
+            vault.add_token(Token::new(name.clone(), value, None, Some("jwt".to_string()), None, None, Vec::new())?)?;
         }
-    };
-    
+        GenerateType::Key => {
+            vault.add_token(Token::new(name.clone(), value, None, Some("key".to_string()), None, None, Vec::new())?)?;
+        }
+        GenerateType::Uuid(_) => {
+            vault.add_token(Token::new(name.clone(), value, None, Some("uuid".to_string()), None, None, Vec::new())?)?;
+        }
+    }
+
+    display::success(&format!("Saved as '{}'.", name));
+    Ok(())
+}
+
+/// Generates an SSH key pair and returns its OpenSSH-encoded
+/// (private, public) forms. Only ed25519 is currently supported.
+fn generate_ssh_keypair(key_type: &str, comment: &str) -> Result<(String, String), CliError> {
+    if key_type != "ed25519" {
+        return Err(CliError::Other(format!(
+            "Unsupported key type '{}'. Only 'ed25519' is currently supported.",
+            key_type
+        )));
+    }
+
+    let mut private_key = PrivateKey::random(&mut rand::thread_rng(), Algorithm::Ed25519)
+        .map_err(|e| CliError::Other(format!("Failed to generate SSH key: {}", e)))?;
+    private_key.set_comment(comment);
+
+    let private_openssh = private_key
+        .to_openssh(LineEnding::LF)
+        .map_err(|e| CliError::Other(format!("Failed to encode private key: {}", e)))?
+        .to_string();
+    let public_openssh = private_key
+        .public_key()
+        .to_openssh()
+        .map_err(|e| CliError::Other(format!("Failed to encode public key: {}", e)))?;
+
+    Ok((private_openssh, public_openssh))
+}
+
+/// Runs `kookie generate ssh`. Handled separately from `run()`/`generate_one`
+/// since a key pair is two values, not the single string the rest of this
+/// module's generators produce.
+pub fn run_ssh(
+    key_type: String,
+    comment: Option<String>,
+    save: bool,
+    name: Option<String>,
+) -> Result<(), CliError> {
+    if save && name.is_none() {
+        return Err(CliError::Other("--save requires --name".to_string()));
+    }
+
+    let comment = comment.unwrap_or_else(|| "kookie".to_string());
+    let (private_key, public_key) = generate_ssh_keypair(&key_type, &comment)?;
+
     println!();
-    println!("{}: {}", label, value);
+    println!("Public key:");
+    println!("{}", public_key);
     println!();
-    
-    if copy {
-        clipboard::copy_to_clipboard(&value)?;
-        display::success("Copied to clipboard!");
+    println!("Private key ({}):", key_type);
+    println!("{}", private_key);
+    println!();
+
+    if save {
+        let mut vault = ensure_unlocked()?;
+        let name = name.expect("checked above");
+        let secret = SshKey::new(
+            name.clone(),
+            private_key,
+            public_key,
+            key_type,
+            None,
+            Some(comment),
+            None,
+            None,
+        )?;
+        vault.add_ssh_key(secret)?;
+        display::success(&format!("Saved as '{}'.", name));
     }
-    
+
     Ok(())
 }
@@ -1,60 +1,445 @@
 //! Get secret command
 
+use crate::cli_error::CliError;
+use crate::commands::list::ListFilter;
 use crate::commands::lock::ensure_unlocked;
+use crate::session::cache;
+use crate::utils::clipboard::ClipboardError;
+use crate::utils::display::{Encoding, RevealMode};
+use crate::utils::input;
 use crate::utils::{clipboard, display};
+use crate::vault::types::Password;
+use crate::vault::PrefixResult;
 
-/// Runs the get command
-pub fn run(id_or_name: &str, copy: bool) -> Result<(), Box<dyn std::error::Error>> {
+/// How `--copy` should combine a password's fields for the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyFormat {
+    /// Just the secret value (the existing behavior)
+    #[default]
+    Value,
+    /// `username<TAB>password`, for tabbing between a login form's fields
+    Login,
+    /// `username<newline>password`
+    Lines,
+}
+
+impl std::str::FromStr for CopyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "value" => Ok(CopyFormat::Value),
+            "login" => Ok(CopyFormat::Login),
+            "lines" => Ok(CopyFormat::Lines),
+            other => Err(format!("Unknown format '{}'. Expected one of: value, login, lines", other)),
+        }
+    }
+}
+
+/// Builds the clipboard string for a password under `format`. Without a
+/// username, `login`/`lines` both degrade to just the password.
+pub(crate) fn format_password_copy(password: &Password, format: CopyFormat) -> String {
+    match (format, &password.username) {
+        (CopyFormat::Login, Some(username)) => format!("{}\t{}", username, password.password),
+        (CopyFormat::Lines, Some(username)) => format!("{}\n{}", username, password.password),
+        _ => password.password.clone(),
+    }
+}
+
+/// Copies to the clipboard, printing a friendly message instead of a raw
+/// error when no clipboard backend is available (e.g. headless/SSH).
+pub(crate) fn copy_or_warn(value: &str, success_msg: &str) -> Result<(), CliError> {
+    match clipboard::copy_to_clipboard(value, true) {
+        Ok(()) => {
+            display::success(success_msg);
+            Ok(())
+        }
+        Err(ClipboardError::Unavailable) => {
+            display::warning("No clipboard available; use --show to print the value");
+            Ok(())
+        }
+        Err(ClipboardError::VerificationMismatch) => {
+            display::warning("Clipboard copy couldn't be verified (read-back didn't match); try again or use --show");
+            Ok(())
+        }
+        Err(ClipboardError::VerificationUnsupported) => {
+            display::success(success_msg);
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Quotes `value` as a single-quoted POSIX shell literal, escaping any
+/// embedded single quotes by closing the quote, emitting an escaped `'`,
+/// then reopening it (the standard `'\''` trick).
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Prints `export VARNAME='value'` for `eval "$(kookie get ... --export VAR)"`,
+/// re-encoding `value` per `encoding` first.
+fn print_export(varname: &str, value: &str, encoding: Encoding) {
+    println!("export {}={}", varname, shell_quote(&display::encode_value(value, encoding)));
+}
+
+/// Prints a single field's raw value (re-encoded per `encoding`), or an
+/// error listing the available fields for that secret type if `name` isn't
+/// recognized.
+fn print_field(value: Option<String>, available: &[impl AsRef<str>], encoding: Encoding) -> Result<(), CliError> {
+    match value {
+        Some(v) => {
+            println!("{}", display::encode_value(&v, encoding));
+            Ok(())
+        }
+        None => {
+            let names: Vec<&str> = available.iter().map(|s| s.as_ref()).collect();
+            Err(CliError::NotFound(format!(
+                "Unknown field. Available fields: {}",
+                names.join(", ")
+            )))
+        }
+    }
+}
+
+/// Deletes the secret via `delete` once it's been shown, if `burn` is set
+/// (and only if showing it actually succeeded). `delete` already saves the
+/// vault via `Vault::mutate`, so there's nothing else to persist here.
+pub(crate) fn burn_if_needed<T>(
+    burn: bool,
+    result: Result<(), CliError>,
+    delete: impl FnOnce() -> Result<T, crate::vault::VaultError>,
+) -> Result<(), CliError> {
+    if burn && result.is_ok() {
+        delete()?;
+        display::warning("Burn-after-read secret deleted - it won't be retrievable again.");
+    }
+    result
+}
+
+/// Resolves `id_or_name` to a concrete id or name, accepting a unique id
+/// prefix (e.g. `kookie get abc12345`) the way git resolves short hashes.
+/// Once resolved, the rest of `run` can keep matching on it exactly as
+/// before. Ambiguous prefixes are reported by listing the full candidates.
+pub(crate) fn resolve_id_or_name(vault: &crate::vault::Vault, id_or_name: &str) -> Result<String, CliError> {
+    match vault.resolve_prefix(id_or_name) {
+        PrefixResult::Unique(entry) => Ok(entry.id),
+        PrefixResult::Ambiguous(matches) => {
+            display::warning(&format!("'{}' matches more than one secret:", id_or_name));
+            for m in &matches {
+                display::list_item(&m.id, &m.name, Some(&m.secret_type.to_string()), m.favorite, false);
+            }
+            Err(CliError::Other(format!(
+                "Ambiguous id prefix '{}'; use a longer prefix or the full id.",
+                id_or_name
+            )))
+        }
+        PrefixResult::None => Ok(id_or_name.to_string()),
+    }
+}
+
+/// Runs `get --all`: iterates every secret matching `filter` and renders
+/// each with its `display_*` function, the same way `list --reveal` does,
+/// but unlike `list` this always shows the full secret body (masked per
+/// `mask`/the configured `reveal_mode` unless `reveal` is given) rather than
+/// just metadata - for a full audit/export-to-screen. `reveal` is gated
+/// behind an interactive confirmation and refuses to run non-interactively,
+/// mirroring `list --reveal`.
+pub fn run_all(filter: ListFilter, reveal: bool, mask: Option<RevealMode>) -> Result<(), CliError> {
     let vault = ensure_unlocked()?;
-    
-    // Search in all secret types
-    if let Some(p) = vault.get_password(id_or_name) {
-        display::display_password(p, true);
-        if copy {
-            clipboard::copy_to_clipboard(&p.password)?;
-            display::success("Password copied to clipboard!");
+
+    if reveal {
+        if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            return Err(CliError::Other(
+                "--reveal requires an interactive terminal; refusing to print all secrets non-interactively."
+                    .to_string(),
+            ));
+        }
+        display::warning("This will print every matching secret's value in full.");
+        if !input::prompt_confirm("Are you sure you want to continue?", false)? {
+            display::info("Aborted.");
+            return Ok(());
         }
-        return Ok(());
     }
-    
-    if let Some(k) = vault.get_api_key(id_or_name) {
-        display::display_api_key(k, true);
-        if copy {
-            clipboard::copy_to_clipboard(&k.key)?;
-            display::success("API key copied to clipboard!");
+
+    let mode = if reveal {
+        RevealMode::Full
+    } else {
+        mask.unwrap_or_else(|| cache::load_config().reveal_mode)
+    };
+
+    let mut total = 0;
+
+    if filter == ListFilter::All || filter == ListFilter::Passwords {
+        for p in &vault.data.passwords {
+            display::display_password(p, mode);
+            total += 1;
         }
-        return Ok(());
     }
-    
-    if let Some(n) = vault.get_note(id_or_name) {
-        display::display_note(n, true);
-        if copy {
-            clipboard::copy_to_clipboard(&n.content)?;
-            display::success("Note content copied to clipboard!");
+    if filter == ListFilter::All || filter == ListFilter::ApiKeys {
+        for k in &vault.data.api_keys {
+            display::display_api_key(k, mode);
+            total += 1;
         }
-        return Ok(());
     }
-    
-    if let Some(c) = vault.get_db_credential(id_or_name) {
-        display::display_db_credential(c, true);
-        if copy {
-            clipboard::copy_to_clipboard(&c.connection_string())?;
-            display::success("Connection string copied to clipboard!");
+    if filter == ListFilter::All || filter == ListFilter::Notes {
+        for n in &vault.data.notes {
+            display::display_note(n, mode);
+            total += 1;
         }
-        return Ok(());
     }
-    
-    if let Some(t) = vault.get_token(id_or_name) {
-        display::display_token(t, true);
-        if copy {
-            clipboard::copy_to_clipboard(&t.token)?;
-            display::success("Token copied to clipboard!");
+    if filter == ListFilter::All || filter == ListFilter::DbCredentials {
+        for c in &vault.data.db_credentials {
+            display::display_db_credential(c, mode);
+            total += 1;
+        }
+    }
+    if filter == ListFilter::All || filter == ListFilter::Tokens {
+        for t in &vault.data.tokens {
+            display::display_token(t, mode);
+            total += 1;
         }
-        return Ok(());
     }
-    
-    display::error(&format!("Secret '{}' not found.", id_or_name));
-    display::info("Use 'kookie list' to see all secrets.");
-    
+    if filter == ListFilter::All || filter == ListFilter::Custom {
+        for c in &vault.data.custom_secrets {
+            display::display_custom(c, mode);
+            total += 1;
+        }
+    }
+    if filter == ListFilter::All || filter == ListFilter::SshKey {
+        for s in &vault.data.ssh_keys {
+            display::display_ssh_key(s, mode);
+            total += 1;
+        }
+    }
+
+    if total == 0 {
+        display::info("No secrets found. Use 'kookie add' to add secrets.");
+    } else {
+        println!();
+        display::info(&format!("Total: {} secrets", total));
+    }
+
     Ok(())
 }
+
+/// Runs the get command
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    id_or_name: &str,
+    copy: bool,
+    reveal_seconds: Option<u64>,
+    field: Option<String>,
+    extract: Option<String>,
+    out: Option<std::path::PathBuf>,
+    mask: Option<RevealMode>,
+    format: CopyFormat,
+    export: Option<String>,
+    encoding: Encoding,
+) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    let mask = mask.unwrap_or_else(|| cache::load_config().reveal_mode);
+
+    let resolved = resolve_id_or_name(&vault, id_or_name)?;
+    let id_or_name = resolved.as_str();
+
+    if let Some(target) = extract {
+        if let Some(s) = vault.get_ssh_key(id_or_name) {
+            let dir = out.unwrap_or_else(|| std::path::PathBuf::from(&target));
+            std::fs::create_dir_all(&dir)?;
+            let private_path = dir.join(&s.name);
+            let public_path = dir.join(format!("{}.pub", s.name));
+            crate::utils::secure_fs::write(&private_path, s.private_key.as_bytes())?;
+            crate::utils::secure_fs::write_with_mode(&public_path, s.public_key.as_bytes(), 0o644)?;
+            display::success(&format!("Extracted SSH key pair to {}", dir.display()));
+            return Ok(());
+        }
+
+        let bytes = vault.extract_attachment(id_or_name, &target)?;
+        let out_path = out.unwrap_or_else(|| std::path::PathBuf::from(&target));
+        crate::utils::secure_fs::write(&out_path, &bytes)?;
+        display::success(&format!("Extracted '{}' to {}", target, out_path.display()));
+        return Ok(());
+    }
+
+    // Search in all secret types. Each match is cloned so the immutable
+    // borrow on `vault` ends before a burn-after-read secret deletes itself
+    // below.
+    if let Some(p) = vault.get_password(id_or_name).cloned() {
+        if p.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &p.password, encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(p.field(f), crate::vault::types::Password::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Password", &display::encode_value(&p.password, encoding), secs),
+                None => display::display_password(&p, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&format_password_copy(&p, format), encoding), "Password copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(p.burn_after_read, result, || vault.delete_password(id_or_name));
+    }
+
+    if let Some(k) = vault.get_api_key(id_or_name).cloned() {
+        if k.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &k.key, encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(k.field(f), crate::vault::types::ApiKey::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Key", &display::encode_value(&k.key, encoding), secs),
+                None => display::display_api_key(&k, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&k.key, encoding), "API key copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(k.burn_after_read, result, || vault.delete_api_key(id_or_name));
+    }
+
+    if let Some(n) = vault.get_note(id_or_name).cloned() {
+        if n.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &n.content, encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(n.field(f), crate::vault::types::Note::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Content", &display::encode_value(&n.content, encoding), secs),
+                None => display::display_note(&n, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&n.content, encoding), "Note content copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(n.burn_after_read, result, || vault.delete_note(id_or_name));
+    }
+
+    if let Some(c) = vault.get_db_credential(id_or_name).cloned() {
+        if c.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &c.connection_string(), encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(c.field(f), crate::vault::types::DbCredential::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Password", &display::encode_value(&c.password, encoding), secs),
+                None => display::display_db_credential(&c, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&c.connection_string(), encoding), "Connection string copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(c.burn_after_read, result, || vault.delete_db_credential(id_or_name));
+    }
+
+    if let Some(t) = vault.get_token(id_or_name).cloned() {
+        if t.is_expired() {
+            vault.delete_token(id_or_name)?;
+            return Err(CliError::NotFound(format!(
+                "Secret '{}' has expired and was removed. Use 'kookie list' to see all secrets.",
+                id_or_name
+            )));
+        }
+        if t.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &t.token, encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(t.field(f), crate::vault::types::Token::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Token", &display::encode_value(&t.token, encoding), secs),
+                None => display::display_token(&t, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&t.token, encoding), "Token copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(t.burn_after_read, result, || vault.delete_token(id_or_name));
+    }
+
+    if let Some(c) = vault.get_custom(id_or_name).cloned() {
+        if c.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if export.is_some() {
+                return Err(CliError::Other(
+                    "Custom secrets have multiple fields; use --field <name> to pick one instead of --export".to_string(),
+                ));
+            }
+            if let Some(f) = &field {
+                return print_field(c.field(f), &c.field_names(), encoding);
+            }
+            display::display_custom(&c, mask);
+            if copy {
+                display::warning("Custom secrets have multiple fields; use --show to print values instead of --copy");
+            }
+            Ok(())
+        })();
+        return burn_if_needed(c.burn_after_read, result, || vault.delete_custom(id_or_name));
+    }
+
+    if let Some(s) = vault.get_ssh_key(id_or_name).cloned() {
+        if s.burn_after_read {
+            display::warning("This secret is burn-after-read; it will be deleted after this access.");
+        }
+        let result = (|| -> Result<(), CliError> {
+            if let Some(varname) = &export {
+                print_export(varname, &s.private_key, encoding);
+                return Ok(());
+            }
+            if let Some(f) = &field {
+                return print_field(s.field(f), crate::vault::types::SshKey::FIELDS, encoding);
+            }
+            match reveal_seconds {
+                Some(secs) => display::print_secret_temporarily("Private key", &display::encode_value(&s.private_key, encoding), secs),
+                None => display::display_ssh_key(&s, mask),
+            }
+            if copy {
+                copy_or_warn(&display::encode_value(&s.private_key, encoding), "Private key copied to clipboard!")?;
+            }
+            Ok(())
+        })();
+        return burn_if_needed(s.burn_after_read, result, || vault.delete_ssh_key(id_or_name));
+    }
+
+    Err(CliError::NotFound(format!(
+        "Secret '{}' not found. Use 'kookie list' to see all secrets.",
+        id_or_name
+    )))
+}
@@ -0,0 +1,53 @@
+//! Git-backed vault versioning command
+//!
+//! `kookie git init` turns the vault directory into a git repository so
+//! `vault.json`'s history - still fully encrypted - can be versioned and
+//! pushed to a private remote like any other file. `log`/`restore` read
+//! that history back. See `vault::gitstore` for the implementation and
+//! `git_autocommit` in `config` for committing automatically after every
+//! change.
+
+use crate::cli_error::CliError;
+use crate::utils::display;
+use crate::vault::storage;
+
+fn vault_dir() -> std::path::PathBuf {
+    storage::get_vault_dir()
+}
+
+/// Runs `kookie git init`
+pub fn init() -> Result<(), CliError> {
+    crate::vault::gitstore::init(&vault_dir())?;
+    display::success("Vault directory is now a git repository.");
+    display::info("Enable 'kookie config set git_autocommit true' to commit after every change.");
+    Ok(())
+}
+
+/// Runs `kookie git log`
+pub fn log() -> Result<(), CliError> {
+    let entries = crate::vault::gitstore::log(&vault_dir())?;
+
+    if entries.is_empty() {
+        display::info("No commits yet.");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        println!(
+            "{}  {}  {}",
+            entry.short_hash,
+            entry.committed_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.message
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs `kookie git restore <commit>`
+pub fn restore(commit: &str) -> Result<(), CliError> {
+    crate::vault::gitstore::restore(&vault_dir(), commit)?;
+    display::success(&format!("Restored vault.json from commit '{}'.", commit));
+    display::warning("The in-memory session, if any, still reflects the old vault; run 'kookie lock' then unlock again.");
+    Ok(())
+}
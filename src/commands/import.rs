@@ -0,0 +1,421 @@
+//! Import secrets from another vault's decrypted JSON, merging into the
+//! current vault with an explicit conflict-resolution strategy.
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::{display, input};
+use crate::vault::types::SecretType;
+use crate::vault::VaultData;
+use std::path::Path;
+
+#[cfg(feature = "age")]
+pub(crate) mod age_backend {
+    use crate::cli_error::CliError;
+    use std::io::Read;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    /// Loads an age identity from a keyfile, skipping blank lines and `#`
+    /// comments the way age's own CLI does, so identity files generated by
+    /// `age-keygen` work unmodified.
+    pub fn decrypt(identity_path: &Path, encrypted: &[u8]) -> Result<Vec<u8>, CliError> {
+        let identity_content = std::fs::read_to_string(identity_path)?;
+        let identity_line = identity_content
+            .lines()
+            .find(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))
+            .ok_or_else(|| CliError::Other(format!("No age identity found in {}", identity_path.display())))?;
+        let identity = age::x25519::Identity::from_str(identity_line.trim())
+            .map_err(|e| CliError::Other(format!("Invalid age identity: {}", e)))?;
+
+        let decryptor = age::Decryptor::new(encrypted)
+            .map_err(|e| CliError::Other(format!("age decryption failed: {}", e)))?;
+        let age::Decryptor::Recipients(decryptor) = decryptor else {
+            return Err(CliError::Other("File is not a recipient-encrypted age file".to_string()));
+        };
+
+        let mut plaintext = Vec::new();
+        let mut reader = decryptor
+            .decrypt(std::iter::once(&identity as &dyn age::Identity))
+            .map_err(|e| CliError::Other(format!("age decryption failed: {}", e)))?;
+        reader
+            .read_to_end(&mut plaintext)
+            .map_err(|e| CliError::Other(format!("age decryption failed: {}", e)))?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(not(feature = "age"))]
+pub(crate) mod age_backend {
+    use crate::cli_error::CliError;
+    use std::path::Path;
+
+    pub fn decrypt(_identity_path: &Path, _encrypted: &[u8]) -> Result<Vec<u8>, CliError> {
+        Err(CliError::Other(
+            "Age-encrypted import requires the 'age' feature; rebuild with `--features age`.".to_string(),
+        ))
+    }
+}
+
+/// How to resolve a name collision between an existing secret and one
+/// being imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Keep the existing secret, drop the incoming one.
+    Skip,
+    /// Replace the existing secret with the incoming one.
+    Overwrite,
+    /// Keep both, appending a suffix to the incoming secret's name.
+    Rename,
+    /// Ask interactively for each conflict. `run` resolves this to one of
+    /// the other three per conflict before calling `merge`.
+    Prompt,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(ConflictStrategy::Skip),
+            "overwrite" => Ok(ConflictStrategy::Overwrite),
+            "rename" => Ok(ConflictStrategy::Rename),
+            "prompt" => Ok(ConflictStrategy::Prompt),
+            other => Err(format!(
+                "Unknown conflict strategy '{}'. Expected one of: skip, overwrite, rename, prompt",
+                other
+            )),
+        }
+    }
+}
+
+/// What a merge did (or, before it's applied, would do) - printed as a
+/// preview and, if anything changed, again as a result.
+#[derive(Default)]
+pub struct MergeReport {
+    pub new: Vec<(SecretType, String)>,
+    pub conflicts: Vec<(SecretType, String)>,
+    pub skipped: Vec<(SecretType, String)>,
+    pub overwritten: Vec<(SecretType, String)>,
+    pub renamed: Vec<(SecretType, String, String)>,
+}
+
+impl MergeReport {
+    #[allow(dead_code)]
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Merges `incoming` into `existing`, resolving every name collision with
+/// `strategy`. Pure and disk-free, so it's straightforward to unit test.
+/// `ConflictStrategy::Prompt` isn't meaningful to a pure function and is
+/// treated the same as `Skip` - `run` resolves it interactively, conflict by
+/// conflict, before ever calling this.
+pub fn merge(existing: &VaultData, incoming: &VaultData, strategy: ConflictStrategy) -> (VaultData, MergeReport) {
+    let mut merged = existing.clone();
+    let mut report = MergeReport::default();
+
+    merge_into(
+        &mut merged.passwords,
+        incoming.passwords.clone(),
+        SecretType::Password,
+        |p| &p.name,
+        |p, name| p.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.api_keys,
+        incoming.api_keys.clone(),
+        SecretType::ApiKey,
+        |k| &k.name,
+        |k, name| k.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.notes,
+        incoming.notes.clone(),
+        SecretType::Note,
+        |n| &n.name,
+        |n, name| n.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.db_credentials,
+        incoming.db_credentials.clone(),
+        SecretType::DbCredential,
+        |c| &c.name,
+        |c, name| c.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.tokens,
+        incoming.tokens.clone(),
+        SecretType::Token,
+        |t| &t.name,
+        |t, name| t.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.custom_secrets,
+        incoming.custom_secrets.clone(),
+        SecretType::Custom,
+        |c| &c.name,
+        |c, name| c.name = name,
+        strategy,
+        &mut report,
+    );
+    merge_into(
+        &mut merged.ssh_keys,
+        incoming.ssh_keys.clone(),
+        SecretType::SshKey,
+        |s| &s.name,
+        |s, name| s.name = name,
+        strategy,
+        &mut report,
+    );
+
+    (merged, report)
+}
+
+/// Merges one secret-type vector's `incoming` items into `existing`,
+/// recording what happened in `report`. Shared by every secret type in
+/// `merge` since the collision logic is identical - only how to read/set a
+/// name differs, which `get_name`/`set_name` supply.
+#[allow(clippy::too_many_arguments)]
+fn merge_into<T>(
+    existing: &mut Vec<T>,
+    incoming: Vec<T>,
+    secret_type: SecretType,
+    get_name: impl Fn(&T) -> &str,
+    set_name: impl Fn(&mut T, String),
+    strategy: ConflictStrategy,
+    report: &mut MergeReport,
+) {
+    for mut item in incoming {
+        match existing.iter().position(|e| get_name(e) == get_name(&item)) {
+            None => {
+                report.new.push((secret_type, get_name(&item).to_string()));
+                existing.push(item);
+            }
+            Some(idx) => {
+                report.conflicts.push((secret_type, get_name(&item).to_string()));
+                match strategy {
+                    ConflictStrategy::Skip | ConflictStrategy::Prompt => {
+                        report.skipped.push((secret_type, get_name(&item).to_string()));
+                    }
+                    ConflictStrategy::Overwrite => {
+                        report.overwritten.push((secret_type, get_name(&item).to_string()));
+                        existing[idx] = item;
+                    }
+                    ConflictStrategy::Rename => {
+                        let old_name = get_name(&item).to_string();
+                        let new_name = format!("{} (imported)", old_name);
+                        set_name(&mut item, new_name.clone());
+                        report.renamed.push((secret_type, old_name, new_name));
+                        existing.push(item);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Prints a conflict-report preview: counts of new vs. conflicting secrets.
+fn print_preview(report: &MergeReport) {
+    display::info(&format!(
+        "{} new secret(s), {} conflict(s) with existing names.",
+        report.new.len(),
+        report.conflicts.len()
+    ));
+    for (secret_type, name) in &report.conflicts {
+        display::info(&format!("  conflict: {} '{}'", secret_type, name));
+    }
+}
+
+/// Runs the import command: reads `path` as another vault's decrypted data
+/// (a JSON-serialized `VaultData`) and merges it into the current vault.
+///
+/// If `age_identity` is given, `path` is instead treated as an age-encrypted
+/// file (as produced by `kookie export --age`) and is decrypted with that
+/// identity before being parsed the same way - everything past that point
+/// is identical regardless of where the plaintext came from.
+pub fn run(path: &Path, on_conflict: ConflictStrategy, age_identity: Option<&Path>) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+
+    let content = match age_identity {
+        Some(identity_path) => {
+            let encrypted = std::fs::read(path)?;
+            age_backend::decrypt(identity_path, &encrypted)?
+        }
+        None => std::fs::read(path)?,
+    };
+    let incoming: VaultData = serde_json::from_slice(&content)?;
+
+    // Dry run with Skip just to build the preview; nothing is written yet.
+    let (_, preview) = merge(&vault.data, &incoming, ConflictStrategy::Skip);
+    print_preview(&preview);
+
+    if preview.new.is_empty() && preview.conflicts.is_empty() {
+        display::info("Nothing to import.");
+        return Ok(());
+    }
+
+    if !input::prompt_confirm("Apply this import?", false)? {
+        display::info("Aborted.");
+        return Ok(());
+    }
+
+    let (merged, report) = if on_conflict == ConflictStrategy::Prompt {
+        resolve_prompt(&vault.data, &incoming, &preview)?
+    } else {
+        merge(&vault.data, &incoming, on_conflict)
+    };
+
+    vault.data = merged;
+    vault.save()?;
+
+    display::success(&format!(
+        "Imported {} new, {} overwritten, {} renamed, {} skipped.",
+        report.new.len(),
+        report.overwritten.len(),
+        report.renamed.len(),
+        report.skipped.len()
+    ));
+    Ok(())
+}
+
+/// Resolves `ConflictStrategy::Prompt` by asking once per conflicting
+/// secret, then running one `merge` per distinct choice so every secret
+/// still goes through the same pure merge logic.
+fn resolve_prompt(
+    existing: &VaultData,
+    incoming: &VaultData,
+    preview: &MergeReport,
+) -> Result<(VaultData, MergeReport), CliError> {
+    let mut to_skip = std::collections::HashSet::new();
+    let mut to_overwrite = std::collections::HashSet::new();
+    let mut to_rename = std::collections::HashSet::new();
+
+    for (secret_type, name) in &preview.conflicts {
+        loop {
+            let choice = input::prompt_text(&format!(
+                "'{}' ({}) already exists - skip/overwrite/rename?",
+                name, secret_type
+            ))?;
+            match choice.to_lowercase().as_str() {
+                "skip" | "s" => {
+                    to_skip.insert((*secret_type, name.clone()));
+                    break;
+                }
+                "overwrite" | "o" => {
+                    to_overwrite.insert((*secret_type, name.clone()));
+                    break;
+                }
+                "rename" | "r" => {
+                    to_rename.insert((*secret_type, name.clone()));
+                    break;
+                }
+                _ => display::warning("Please answer skip, overwrite, or rename."),
+            }
+        }
+    }
+
+    // Merge once per resolution so the real work still goes through the
+    // pure `merge` function - each pass only touches the secrets destined
+    // for that strategy, via a filtered copy of `incoming`.
+    let only = |keep: &std::collections::HashSet<(SecretType, String)>| {
+        let mut filtered = incoming.clone();
+        filtered.passwords.retain(|p| keep.contains(&(SecretType::Password, p.name.clone())));
+        filtered.api_keys.retain(|k| keep.contains(&(SecretType::ApiKey, k.name.clone())));
+        filtered.notes.retain(|n| keep.contains(&(SecretType::Note, n.name.clone())));
+        filtered.db_credentials.retain(|c| keep.contains(&(SecretType::DbCredential, c.name.clone())));
+        filtered.tokens.retain(|t| keep.contains(&(SecretType::Token, t.name.clone())));
+        filtered.custom_secrets.retain(|c| keep.contains(&(SecretType::Custom, c.name.clone())));
+        filtered.ssh_keys.retain(|s| keep.contains(&(SecretType::SshKey, s.name.clone())));
+        filtered
+    };
+
+    let (merged, mut report) = merge(existing, &only(&to_overwrite), ConflictStrategy::Overwrite);
+    let (merged, rename_report) = merge(&merged, &only(&to_rename), ConflictStrategy::Rename);
+    report.renamed = rename_report.renamed;
+    report.skipped = to_skip.into_iter().collect();
+
+    // Finally bring in every non-conflicting secret, which no strategy
+    // branch above touches either way.
+    let (merged, new_report) = merge(&merged, incoming, ConflictStrategy::Skip);
+    report.new = new_report.new;
+
+    Ok((merged, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::types::Password;
+
+    fn password(name: &str, value: &str) -> Password {
+        Password::new(name.to_string(), value.to_string(), None, None, None, None, None, None).unwrap()
+    }
+
+    fn vault_with(passwords: Vec<Password>) -> VaultData {
+        VaultData { passwords, ..VaultData::default() }
+    }
+
+    #[test]
+    fn test_merge_adds_non_conflicting_secrets() {
+        let existing = vault_with(vec![password("github", "old-pw")]);
+        let incoming = vault_with(vec![password("gitlab", "new-pw")]);
+
+        let (merged, report) = merge(&existing, &incoming, ConflictStrategy::Skip);
+
+        assert_eq!(merged.passwords.len(), 2);
+        assert_eq!(report.new, vec![(SecretType::Password, "gitlab".to_string())]);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_skip_keeps_existing_value() {
+        let existing = vault_with(vec![password("github", "old-pw")]);
+        let incoming = vault_with(vec![password("github", "new-pw")]);
+
+        let (merged, report) = merge(&existing, &incoming, ConflictStrategy::Skip);
+
+        assert_eq!(merged.passwords.len(), 1);
+        assert_eq!(merged.passwords[0].password, "old-pw");
+        assert_eq!(report.conflicts, vec![(SecretType::Password, "github".to_string())]);
+        assert_eq!(report.skipped, vec![(SecretType::Password, "github".to_string())]);
+    }
+
+    #[test]
+    fn test_merge_overwrite_replaces_existing_value() {
+        let existing = vault_with(vec![password("github", "old-pw")]);
+        let incoming = vault_with(vec![password("github", "new-pw")]);
+
+        let (merged, report) = merge(&existing, &incoming, ConflictStrategy::Overwrite);
+
+        assert_eq!(merged.passwords.len(), 1);
+        assert_eq!(merged.passwords[0].password, "new-pw");
+        assert_eq!(report.overwritten, vec![(SecretType::Password, "github".to_string())]);
+    }
+
+    #[test]
+    fn test_merge_rename_keeps_both() {
+        let existing = vault_with(vec![password("github", "old-pw")]);
+        let incoming = vault_with(vec![password("github", "new-pw")]);
+
+        let (merged, report) = merge(&existing, &incoming, ConflictStrategy::Rename);
+
+        assert_eq!(merged.passwords.len(), 2);
+        assert!(merged.passwords.iter().any(|p| p.name == "github" && p.password == "old-pw"));
+        assert!(merged.passwords.iter().any(|p| p.name == "github (imported)" && p.password == "new-pw"));
+        assert_eq!(
+            report.renamed,
+            vec![(SecretType::Password, "github".to_string(), "github (imported)".to_string())]
+        );
+    }
+}
@@ -1,44 +1,141 @@
 //! Initialize vault command
 
+use crate::cli_error::CliError;
+use crate::commands::import::{self, ConflictStrategy};
+use crate::crypto::kdf::KdfProfile;
 use crate::utils::{display, input};
-use crate::vault::Vault;
+use crate::vault::{Vault, VaultData};
+use std::env;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the master password for scripted/CI init,
+/// checked before falling back to stdin.
+const MASTER_PASSWORD_ENV: &str = "KOOKIE_MASTER_PASSWORD";
+
+/// Reads `path` as a backup to restore via `--import`: an age-encrypted
+/// file if `age_identity` is given (decrypted the same way `kookie import
+/// --age-identity` does), otherwise a plaintext `VaultData` JSON file as
+/// written by `kookie export`/`kookie import` without `--age`.
+///
+/// Called before anything about the new vault is touched, so a backup that
+/// can't be read or decrypted fails `run` before it creates a vault - not
+/// after, which would leave a freshly initialized, still-empty vault behind.
+fn load_backup(path: &Path, age_identity: Option<&Path>) -> Result<VaultData, CliError> {
+    let content = match age_identity {
+        Some(identity_path) => {
+            let encrypted = std::fs::read(path)?;
+            import::age_backend::decrypt(identity_path, &encrypted)?
+        }
+        None => std::fs::read(path)?,
+    };
+    Ok(serde_json::from_slice(&content)?)
+}
 
 /// Runs the init command
-pub fn run(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `from_stdin_password` forces the non-interactive path even when stdin
+/// happens to be a TTY (useful for tests that pipe a password in). Otherwise
+/// non-interactive mode is detected automatically when stdin is not a TTY.
+///
+/// `import`, if given, is a backup to restore into the vault immediately
+/// after it's initialized - composing `init` and `import` into one step for
+/// new-machine setup. `age_identity` decrypts it first if it's age-encrypted.
+pub fn run(
+    force: bool,
+    from_stdin_password: bool,
+    kdf_profile: Option<String>,
+    yes: bool,
+    import_path: Option<PathBuf>,
+    age_identity: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let profile: KdfProfile = match kdf_profile {
+        Some(p) => p.parse().map_err(|e: String| CliError::Other(e))?,
+        None => KdfProfile::Standard,
+    };
+
+    let backup = import_path.as_deref().map(|path| load_backup(path, age_identity.as_deref())).transpose()?;
+
+    let non_interactive = from_stdin_password || !std::io::stdin().is_terminal();
+
     let mut vault = Vault::new();
-    
+
     if vault.exists() && !force {
-        display::error("Vault already exists at ~/.kookie/vault.json");
-        display::info("Use --force to reinitialize (this will delete all secrets!)");
-        return Ok(());
+        return Err(CliError::Other(
+            "Vault already exists at ~/.kookie/vault.json. Use --force to reinitialize (this will delete all secrets!)"
+                .to_string(),
+        ));
     }
-    
+
     if vault.exists() && force {
-        display::warning("This will delete all existing secrets!");
-        if !input::prompt_confirm("Are you sure you want to continue?", false)? {
-            display::info("Aborted.");
-            return Ok(());
+        if non_interactive {
+            // CI can't silently wipe a real vault - require an explicit --yes.
+            if !yes {
+                return Err(CliError::Other(
+                    "Refusing to overwrite an existing vault in non-interactive mode without --yes.".to_string(),
+                ));
+            }
+        } else {
+            display::warning("This will delete all existing secrets!");
+            if !input::prompt_confirm("Are you sure you want to continue?", false)? {
+                display::info("Aborted.");
+                return Ok(());
+            }
         }
     }
-    
-    println!();
-    display::info("Initializing new kookie vault...");
-    println!();
-    
-    // Prompt for master password
-    let password = input::prompt_new_password("Enter master password:")?;
-    
+
+    let password = if non_interactive {
+        match env::var(MASTER_PASSWORD_ENV) {
+            Ok(p) => p,
+            Err(_) => {
+                let mut line = String::new();
+                std::io::stdin().read_line(&mut line)?;
+                line.trim().to_string()
+            }
+        }
+    } else {
+        println!();
+        display::info("Initializing new kookie vault...");
+        println!();
+        input::prompt_new_password("Enter master password:")?
+    };
+
+    let hint = if non_interactive {
+        None
+    } else {
+        display::info(
+            "A password hint is stored unencrypted and shown after a failed unlock - don't let it reveal the password itself.",
+        );
+        input::prompt_optional("Password hint (optional):")?
+    };
+
+    vault.set_hint(hint);
+    let config = crate::session::cache::load_config();
+    vault.set_storage_backend(config.storage_backend);
+    vault.set_normalize_names(config.normalize_names);
+
     // Initialize vault
     if force {
-        vault.init_force(&password)?;
+        vault.init_force_with_profile(&password, profile)?;
     } else {
-        vault.init(&password)?;
+        vault.init_with_profile(&password, profile)?;
     }
-    
-    println!();
-    display::success("Vault initialized successfully!");
-    display::info("Your encrypted vault is stored at ~/.kookie/vault.json");
-    display::info("Remember your master password - it cannot be recovered!");
-    
+
+    if non_interactive {
+        display::success("Vault initialized.");
+    } else {
+        println!();
+        display::success("Vault initialized successfully!");
+        display::info("Your encrypted vault is stored at ~/.kookie/vault.json");
+        display::info("Remember your master password - it cannot be recovered!");
+    }
+
+    if let Some(incoming) = backup {
+        let (merged, report) = import::merge(&vault.data, &incoming, ConflictStrategy::Skip);
+        vault.data = merged;
+        vault.save()?;
+        display::success(&format!("Restored {} secret(s) from backup.", report.new.len()));
+    }
+
     Ok(())
 }
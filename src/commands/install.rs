@@ -5,7 +5,7 @@
 use crate::utils::display;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(windows)]
 use std::process::Command;
@@ -35,6 +35,17 @@ fn get_install_dir() -> PathBuf {
     }
 }
 
+/// Returns whether the install directory appears in the current process's
+/// PATH. Used by `kookie doctor` to flag an install that isn't reachable
+/// from a shell yet.
+pub(crate) fn is_install_dir_on_path() -> bool {
+    let install_dir = get_install_dir();
+    let install_dir_str = install_dir.to_string_lossy();
+    env::var("PATH")
+        .map(|path| path.contains(&*install_dir_str))
+        .unwrap_or(false)
+}
+
 /// Gets the binary name for the current OS
 fn get_binary_name() -> &'static str {
     #[cfg(windows)]
@@ -49,7 +60,7 @@ fn get_binary_name() -> &'static str {
 
 /// Adds a directory to the user's PATH on Windows
 #[cfg(windows)]
-fn add_to_path_windows(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error::Error>> {
+fn add_to_path_windows(install_dir: &Path) -> Result<bool, crate::cli_error::CliError> {
     use winreg::enums::*;
     use winreg::RegKey;
     
@@ -87,7 +98,7 @@ fn add_to_path_windows(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error
 
 /// Adds to PATH on Unix systems
 #[cfg(not(windows))]
-fn add_to_path_unix(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error::Error>> {
+fn add_to_path_unix(install_dir: &Path) -> Result<bool, crate::cli_error::CliError> {
     let install_dir_str = install_dir.to_string_lossy();
     
     // Check if already in PATH
@@ -137,7 +148,7 @@ fn add_to_path_unix(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error::E
 }
 
 /// Runs the install command
-pub fn run(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+pub fn run(force: bool) -> Result<(), crate::cli_error::CliError> {
     println!();
     display::info("🍪 Installing Kookie CLI...");
     println!();
@@ -221,7 +232,7 @@ pub fn run(force: bool) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 /// Runs the uninstall command
-pub fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
+pub fn uninstall() -> Result<(), crate::cli_error::CliError> {
     let install_dir = get_install_dir();
     let binary_name = get_binary_name();
     let installed_path = install_dir.join(binary_name);
@@ -1,7 +1,11 @@
 //! List secrets command
 
+use crate::cli_error::CliError;
 use crate::commands::lock::ensure_unlocked;
-use crate::utils::display;
+use crate::session::cache;
+use crate::utils::{display, input};
+use chrono::{DateTime, Utc};
+use std::io::IsTerminal;
 
 /// Type filter for listing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,72 +16,619 @@ pub enum ListFilter {
     Notes,
     DbCredentials,
     Tokens,
+    Custom,
+    SshKey,
+}
+
+/// Output format for `list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListFormat {
+    /// One bulleted line per secret, grouped under a header per type
+    #[default]
+    Bullet,
+    /// Aligned columns (Name, Type, Username/Service, Created, Tags)
+    Table,
+}
+
+impl std::str::FromStr for ListFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bullet" => Ok(ListFormat::Bullet),
+            "table" => Ok(ListFormat::Table),
+            other => Err(format!("Unknown format '{}'. Expected one of: bullet, table", other)),
+        }
+    }
+}
+
+/// Sort order for `list --sort`. Applies to the secret types that carry an
+/// `expires_at` (passwords, API keys, tokens); every other type keeps its
+/// default favorites-first ordering regardless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    /// Already-expired entries first, then soonest-expiring, then entries
+    /// with no `expires_at` last.
+    Expiry,
+}
+
+impl std::str::FromStr for SortMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "expiry" => Ok(SortMode::Expiry),
+            other => Err(format!("Unknown sort mode '{}'. Expected: expiry", other)),
+        }
+    }
+}
+
+/// Compares two `expires_at` values so that `None` ("never expires") sorts
+/// last, and `Some` values sort ascending - already-past timestamps (i.e.
+/// expired) naturally come before future ones, soonest-future first.
+fn cmp_expiry(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> std::cmp::Ordering {
+    match (a, b) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (Some(x), Some(y)) => x.cmp(&y),
+    }
+}
+
+/// Sorts a slice of `(id, name, extra, favorite)` tuples so favorites come
+/// first, preserving original order within each group.
+fn favorites_first<'a>(mut items: Vec<(&'a str, &'a str, Option<String>, bool)>) -> Vec<(&'a str, &'a str, Option<String>, bool)> {
+    items.sort_by_key(|(_, _, _, favorite)| !favorite);
+    items
 }
 
 /// Runs the list command
-pub fn run(filter: ListFilter) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `reveal`, after an interactive confirmation, prints each secret with its
+/// `display_*` function (full value shown) instead of the masked summary
+/// line - intentionally dangerous, so it refuses to run non-interactively.
+/// `favorites_only` restricts the listing to starred secrets; otherwise,
+/// favorites still sort first within each section and get a ★ marker.
+/// `format` picks between the default bullet list and `--format table`.
+/// `count_only` skips listing items entirely and prints per-type counts
+/// (honoring `favorites_only`), as plain text or, with `json`, a single
+/// JSON object - useful for dashboards that just want vault growth over
+/// time without iterating every secret. `env` further restricts database
+/// credentials to those tagged with that environment (e.g. `--db --env
+/// prod`); it has no effect on other secret types. `names_only` skips all
+/// decoration and prints just the matching names, one per line (or
+/// NUL-separated with `null`), for piping into `xargs`. `sort`, when set to
+/// `SortMode::Expiry`, reorders passwords, API keys, and tokens so
+/// already-expired ones come first in red, then soonest-expiring, then
+/// entries with no `expires_at` last; every other type's ordering is
+/// unaffected.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    filter: ListFilter,
+    reveal: bool,
+    favorites_only: bool,
+    format: ListFormat,
+    count_only: bool,
+    json: bool,
+    env: Option<String>,
+    names_only: bool,
+    null: bool,
+    sort: Option<SortMode>,
+) -> Result<(), CliError> {
     let vault = ensure_unlocked()?;
-    
+
+    if names_only {
+        return print_names_only(&vault, filter, favorites_only, env.as_deref(), null);
+    }
+
+    if count_only {
+        return print_counts(&vault, favorites_only, json);
+    }
+
+    if reveal && format == ListFormat::Table {
+        return Err(CliError::Other(
+            "--reveal isn't supported with --format table; use the default bullet format to reveal secrets."
+                .to_string(),
+        ));
+    }
+
+    if format == ListFormat::Table {
+        return render_table(&vault, filter, favorites_only, env.as_deref());
+    }
+
+    let reveal_mode = cache::load_config().reveal_mode;
+
+    if reveal {
+        if !std::io::stdin().is_terminal() {
+            return Err(CliError::Other(
+                "--reveal requires an interactive terminal; refusing to print all secrets non-interactively."
+                    .to_string(),
+            ));
+        }
+        display::warning(&format!(
+            "This will print every matching secret's value ({} mode).",
+            reveal_mode
+        ));
+        if !input::prompt_confirm("Are you sure you want to continue?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+
     let mut total = 0;
-    
+
     if filter == ListFilter::All || filter == ListFilter::Passwords {
-        if !vault.data.passwords.is_empty() {
-            display::list_header("Passwords", vault.data.passwords.len());
-            for p in &vault.data.passwords {
-                display::list_item(&p.id, &p.name, p.username.as_deref());
+        let items: Vec<_> = vault
+            .data
+            .passwords
+            .iter()
+            .filter(|p| !favorites_only || p.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("Passwords", items.len());
+            let mut rows: Vec<_> = items
+                .iter()
+                .map(|p| {
+                    let mut parts = Vec::new();
+                    if let Some(u) = &p.username {
+                        parts.push(u.clone());
+                    }
+                    if p.is_due_for_rotation() {
+                        parts.push("due for rotation".to_string());
+                    }
+                    if p.is_expired() {
+                        parts.push("expired".to_string());
+                    }
+                    let extra = if parts.is_empty() { None } else { Some(parts.join(", ")) };
+                    (p.id.as_str(), p.name.as_str(), extra, p.favorite, p.expires_at)
+                })
+                .collect();
+            if sort == Some(SortMode::Expiry) {
+                rows.sort_by(|a, b| cmp_expiry(a.4, b.4));
+            } else {
+                rows = favorites_first(
+                    rows.into_iter().map(|(id, name, extra, favorite, _)| (id, name, extra, favorite)).collect(),
+                )
+                .into_iter()
+                .map(|(id, name, extra, favorite)| (id, name, extra, favorite, None))
+                .collect();
+            }
+            for (id, name, extra, favorite, _) in &rows {
+                if reveal {
+                    display::display_password(vault.get_password(id).unwrap(), reveal_mode);
+                } else {
+                    let flagged =
+                        vault.get_password(id).is_some_and(|p| p.is_due_for_rotation() || p.is_expired());
+                    display::list_item(id, name, extra.as_deref(), *favorite, flagged);
+                }
             }
-            total += vault.data.passwords.len();
+            total += items.len();
         }
     }
-    
+
     if filter == ListFilter::All || filter == ListFilter::ApiKeys {
-        if !vault.data.api_keys.is_empty() {
-            display::list_header("API Keys", vault.data.api_keys.len());
-            for k in &vault.data.api_keys {
-                display::list_item(&k.id, &k.name, k.service.as_deref());
+        let items: Vec<_> = vault
+            .data
+            .api_keys
+            .iter()
+            .filter(|k| !favorites_only || k.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("API Keys", items.len());
+            let mut rows: Vec<_> = items
+                .iter()
+                .map(|k| {
+                    let mut parts = Vec::new();
+                    if let Some(s) = &k.service {
+                        parts.push(s.clone());
+                    }
+                    if k.is_expired() {
+                        parts.push("expired".to_string());
+                    }
+                    let extra = if parts.is_empty() { None } else { Some(parts.join(", ")) };
+                    (k.id.as_str(), k.name.as_str(), extra, k.favorite, k.expires_at)
+                })
+                .collect();
+            if sort == Some(SortMode::Expiry) {
+                rows.sort_by(|a, b| cmp_expiry(a.4, b.4));
+            } else {
+                rows = favorites_first(
+                    rows.into_iter().map(|(id, name, extra, favorite, _)| (id, name, extra, favorite)).collect(),
+                )
+                .into_iter()
+                .map(|(id, name, extra, favorite)| (id, name, extra, favorite, None))
+                .collect();
+            }
+            for (id, name, extra, favorite, _) in &rows {
+                if reveal {
+                    display::display_api_key(vault.get_api_key(id).unwrap(), reveal_mode);
+                } else {
+                    let expired = vault.get_api_key(id).is_some_and(|k| k.is_expired());
+                    display::list_item(id, name, extra.as_deref(), *favorite, expired);
+                }
             }
-            total += vault.data.api_keys.len();
+            total += items.len();
         }
     }
-    
+
     if filter == ListFilter::All || filter == ListFilter::Notes {
-        if !vault.data.notes.is_empty() {
-            display::list_header("Notes", vault.data.notes.len());
-            for n in &vault.data.notes {
-                display::list_item(&n.id, &n.name, None);
+        let items: Vec<_> = vault
+            .data
+            .notes
+            .iter()
+            .filter(|n| !favorites_only || n.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("Notes", items.len());
+            let sorted = favorites_first(
+                items.iter().map(|n| (n.id.as_str(), n.name.as_str(), None, n.favorite)).collect(),
+            );
+            for (id, name, extra, favorite) in &sorted {
+                if reveal {
+                    display::display_note(vault.get_note(id).unwrap(), reveal_mode);
+                } else {
+                    display::list_item(id, name, extra.as_deref(), *favorite, false);
+                }
             }
-            total += vault.data.notes.len();
+            total += items.len();
         }
     }
-    
+
     if filter == ListFilter::All || filter == ListFilter::DbCredentials {
-        if !vault.data.db_credentials.is_empty() {
-            display::list_header("Database Credentials", vault.data.db_credentials.len());
-            for c in &vault.data.db_credentials {
-                let extra = format!("{}@{}", c.username, c.host);
-                display::list_item(&c.id, &c.name, Some(&extra));
+        let items: Vec<_> = vault
+            .data
+            .db_credentials
+            .iter()
+            .filter(|c| !favorites_only || c.favorite)
+            .filter(|c| env.is_none() || c.environment.as_deref() == env.as_deref())
+            .collect();
+        if !items.is_empty() {
+            display::list_header("Database Credentials", items.len());
+            let sorted = favorites_first(
+                items
+                    .iter()
+                    .map(|c| {
+                        let extra = match &c.environment {
+                            Some(env) => format!("{}@{} [{}]", c.username, c.host, env),
+                            None => format!("{}@{}", c.username, c.host),
+                        };
+                        (c.id.as_str(), c.name.as_str(), Some(extra), c.favorite)
+                    })
+                    .collect(),
+            );
+            for (id, name, extra, favorite) in &sorted {
+                if reveal {
+                    display::display_db_credential(vault.get_db_credential(id).unwrap(), reveal_mode);
+                } else {
+                    display::list_item(id, name, extra.as_deref(), *favorite, false);
+                }
             }
-            total += vault.data.db_credentials.len();
+            total += items.len();
         }
     }
-    
+
     if filter == ListFilter::All || filter == ListFilter::Tokens {
-        if !vault.data.tokens.is_empty() {
-            display::list_header("Tokens", vault.data.tokens.len());
-            for t in &vault.data.tokens {
-                let extra = if t.is_expired() { Some("expired") } else { None };
-                display::list_item(&t.id, &t.name, extra);
+        let items: Vec<_> = vault
+            .data
+            .tokens
+            .iter()
+            .filter(|t| !favorites_only || t.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("Tokens", items.len());
+            let mut token_rows: Vec<_> = items
+                .iter()
+                .map(|t| {
+                    let extra = if t.is_expired() { Some("expired".to_string()) } else { None };
+                    (t.id.as_str(), t.name.as_str(), extra, t.favorite, t.expires_at)
+                })
+                .collect();
+            if sort == Some(SortMode::Expiry) {
+                token_rows.sort_by(|a, b| cmp_expiry(a.4, b.4));
+            } else {
+                token_rows = favorites_first(
+                    token_rows.into_iter().map(|(id, name, extra, favorite, _)| (id, name, extra, favorite)).collect(),
+                )
+                .into_iter()
+                .map(|(id, name, extra, favorite)| (id, name, extra, favorite, None))
+                .collect();
+            }
+            for (id, name, extra, favorite, _) in &token_rows {
+                if reveal {
+                    display::display_token(vault.get_token(id).unwrap(), reveal_mode);
+                } else {
+                    display::list_item(id, name, extra.as_deref(), *favorite, extra.is_some());
+                }
+            }
+            total += items.len();
+        }
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::Custom {
+        let items: Vec<_> = vault
+            .data
+            .custom_secrets
+            .iter()
+            .filter(|c| !favorites_only || c.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("Custom Secrets", items.len());
+            let sorted = favorites_first(
+                items
+                    .iter()
+                    .map(|c| {
+                        (
+                            c.id.as_str(),
+                            c.name.as_str(),
+                            Some(format!("{} fields", c.fields.len())),
+                            c.favorite,
+                        )
+                    })
+                    .collect(),
+            );
+            for (id, name, extra, favorite) in &sorted {
+                if reveal {
+                    display::display_custom(vault.get_custom(id).unwrap(), reveal_mode);
+                } else {
+                    display::list_item(id, name, extra.as_deref(), *favorite, false);
+                }
+            }
+            total += items.len();
+        }
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::SshKey {
+        let items: Vec<_> = vault
+            .data
+            .ssh_keys
+            .iter()
+            .filter(|s| !favorites_only || s.favorite)
+            .collect();
+        if !items.is_empty() {
+            display::list_header("SSH Keys", items.len());
+            let sorted = favorites_first(
+                items
+                    .iter()
+                    .map(|s| (s.id.as_str(), s.name.as_str(), Some(s.key_type.clone()), s.favorite))
+                    .collect(),
+            );
+            for (id, name, extra, favorite) in &sorted {
+                if reveal {
+                    display::display_ssh_key(vault.get_ssh_key(id).unwrap(), reveal_mode);
+                } else {
+                    display::list_item(id, name, extra.as_deref(), *favorite, false);
+                }
             }
-            total += vault.data.tokens.len();
+            total += items.len();
         }
     }
-    
+
     if total == 0 {
-        display::info("No secrets found. Use 'kookie add' to add secrets.");
+        if favorites_only {
+            display::info("No favorites found. Use 'kookie fav <name>' to star a secret.");
+        } else {
+            display::info("No secrets found. Use 'kookie add' to add secrets.");
+        }
     } else {
         println!();
         display::info(&format!("Total: {} secrets", total));
     }
-    
+
+    Ok(())
+}
+
+/// Prints per-type secret counts, honoring `favorites_only`, as either a
+/// compact `key: value, ...` summary or a single JSON object.
+fn print_counts(vault: &crate::vault::Vault, favorites_only: bool, json: bool) -> Result<(), CliError> {
+    let passwords = vault.data.passwords.iter().filter(|p| !favorites_only || p.favorite).count();
+    let api_keys = vault.data.api_keys.iter().filter(|k| !favorites_only || k.favorite).count();
+    let notes = vault.data.notes.iter().filter(|n| !favorites_only || n.favorite).count();
+    let db = vault.data.db_credentials.iter().filter(|c| !favorites_only || c.favorite).count();
+    let tokens = vault.data.tokens.iter().filter(|t| !favorites_only || t.favorite).count();
+    let custom = vault.data.custom_secrets.iter().filter(|c| !favorites_only || c.favorite).count();
+    let ssh_keys = vault.data.ssh_keys.iter().filter(|s| !favorites_only || s.favorite).count();
+    let total = passwords + api_keys + notes + db + tokens + custom + ssh_keys;
+
+    if json {
+        println!(
+            "{{\"passwords\":{},\"api_keys\":{},\"notes\":{},\"db\":{},\"tokens\":{},\"custom\":{},\"ssh_keys\":{},\"total\":{}}}",
+            passwords, api_keys, notes, db, tokens, custom, ssh_keys, total
+        );
+    } else {
+        println!(
+            "passwords: {}, api_keys: {}, notes: {}, db: {}, tokens: {}, custom: {}, ssh_keys: {}, total: {}",
+            passwords, api_keys, notes, db, tokens, custom, ssh_keys, total
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints only the matching secrets' names, one per `\n` (or NUL-separated
+/// with `null`), respecting `filter`, `favorites_only`, and `env` the same
+/// way the bullet format does, with none of `display`'s decoration. Meant
+/// to be piped into `xargs`, e.g. `kookie list --names-only --tokens |
+/// xargs -n1 kookie get`.
+fn print_names_only(
+    vault: &crate::vault::Vault,
+    filter: ListFilter,
+    favorites_only: bool,
+    env: Option<&str>,
+    null: bool,
+) -> Result<(), CliError> {
+    let mut names: Vec<&str> = Vec::new();
+
+    if filter == ListFilter::All || filter == ListFilter::Passwords {
+        names.extend(vault.data.passwords.iter().filter(|p| !favorites_only || p.favorite).map(|p| p.name.as_str()));
+    }
+    if filter == ListFilter::All || filter == ListFilter::ApiKeys {
+        names.extend(vault.data.api_keys.iter().filter(|k| !favorites_only || k.favorite).map(|k| k.name.as_str()));
+    }
+    if filter == ListFilter::All || filter == ListFilter::Notes {
+        names.extend(vault.data.notes.iter().filter(|n| !favorites_only || n.favorite).map(|n| n.name.as_str()));
+    }
+    if filter == ListFilter::All || filter == ListFilter::DbCredentials {
+        names.extend(
+            vault
+                .data
+                .db_credentials
+                .iter()
+                .filter(|c| !favorites_only || c.favorite)
+                .filter(|c| env.is_none() || c.environment.as_deref() == env)
+                .map(|c| c.name.as_str()),
+        );
+    }
+    if filter == ListFilter::All || filter == ListFilter::Tokens {
+        names.extend(vault.data.tokens.iter().filter(|t| !favorites_only || t.favorite).map(|t| t.name.as_str()));
+    }
+    if filter == ListFilter::All || filter == ListFilter::Custom {
+        names.extend(
+            vault.data.custom_secrets.iter().filter(|c| !favorites_only || c.favorite).map(|c| c.name.as_str()),
+        );
+    }
+    if filter == ListFilter::All || filter == ListFilter::SshKey {
+        names.extend(vault.data.ssh_keys.iter().filter(|s| !favorites_only || s.favorite).map(|s| s.name.as_str()));
+    }
+
+    use std::io::Write;
+    let mut stdout = std::io::stdout();
+    for name in names {
+        if null {
+            write!(stdout, "{}\0", name)?;
+        } else {
+            writeln!(stdout, "{}", name)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the metadata rows for `--format table`, respecting `filter` and
+/// `favorites_only` the same way the bullet format does, then renders them
+/// as a single aligned table (favorites sorted first). API keys and tokens
+/// carry tags (see `kookie exec --tag`) and database credentials carry an
+/// environment (see `kookie list --db --env prod`) in this column - other
+/// types show "-". `env` restricts database credentials to that
+/// environment, same as the bullet format.
+fn render_table(
+    vault: &crate::vault::Vault,
+    filter: ListFilter,
+    favorites_only: bool,
+    env: Option<&str>,
+) -> Result<(), CliError> {
+    let mut rows: Vec<display::TableRow> = Vec::new();
+
+    if filter == ListFilter::All || filter == ListFilter::Passwords {
+        rows.extend(vault.data.passwords.iter().filter(|p| !favorites_only || p.favorite).map(|p| {
+            display::TableRow {
+                name: p.name.clone(),
+                secret_type: "Password",
+                username_or_service: p.username.clone().unwrap_or_default(),
+                created_at: p.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: "-".to_string(),
+                favorite: p.favorite,
+            }
+        }));
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::ApiKeys {
+        rows.extend(vault.data.api_keys.iter().filter(|k| !favorites_only || k.favorite).map(|k| {
+            display::TableRow {
+                name: k.name.clone(),
+                secret_type: "API Key",
+                username_or_service: k.service.clone().unwrap_or_default(),
+                created_at: k.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: if k.tags.is_empty() { "-".to_string() } else { k.tags.join(", ") },
+                favorite: k.favorite,
+            }
+        }));
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::Notes {
+        rows.extend(vault.data.notes.iter().filter(|n| !favorites_only || n.favorite).map(|n| {
+            display::TableRow {
+                name: n.name.clone(),
+                secret_type: "Note",
+                username_or_service: String::new(),
+                created_at: n.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: "-".to_string(),
+                favorite: n.favorite,
+            }
+        }));
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::DbCredentials {
+        rows.extend(
+            vault
+                .data
+                .db_credentials
+                .iter()
+                .filter(|c| !favorites_only || c.favorite)
+                .filter(|c| env.is_none() || c.environment.as_deref() == env)
+                .map(|c| display::TableRow {
+                    name: c.name.clone(),
+                    secret_type: "DB Credential",
+                    username_or_service: format!("{}@{}", c.username, c.host),
+                    created_at: c.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    tags: c.environment.clone().unwrap_or_else(|| "-".to_string()),
+                    favorite: c.favorite,
+                }),
+        );
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::Tokens {
+        rows.extend(vault.data.tokens.iter().filter(|t| !favorites_only || t.favorite).map(|t| {
+            display::TableRow {
+                name: t.name.clone(),
+                secret_type: "Token",
+                username_or_service: t.token_type.clone().unwrap_or_default(),
+                created_at: t.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: if t.tags.is_empty() { "-".to_string() } else { t.tags.join(", ") },
+                favorite: t.favorite,
+            }
+        }));
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::Custom {
+        rows.extend(vault.data.custom_secrets.iter().filter(|c| !favorites_only || c.favorite).map(|c| {
+            display::TableRow {
+                name: c.name.clone(),
+                secret_type: "Custom",
+                username_or_service: String::new(),
+                created_at: c.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: "-".to_string(),
+                favorite: c.favorite,
+            }
+        }));
+    }
+
+    if filter == ListFilter::All || filter == ListFilter::SshKey {
+        rows.extend(vault.data.ssh_keys.iter().filter(|s| !favorites_only || s.favorite).map(|s| {
+            display::TableRow {
+                name: s.name.clone(),
+                secret_type: "SSH Key",
+                username_or_service: s.key_type.clone(),
+                created_at: s.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                tags: "-".to_string(),
+                favorite: s.favorite,
+            }
+        }));
+    }
+
+    if rows.is_empty() {
+        if favorites_only {
+            display::info("No favorites found. Use 'kookie fav <name>' to star a secret.");
+        } else {
+            display::info("No secrets found. Use 'kookie add' to add secrets.");
+        }
+        return Ok(());
+    }
+
+    rows.sort_by_key(|r| !r.favorite);
+    let total = rows.len();
+    display::print_table(&rows);
+    println!();
+    display::info(&format!("Total: {} secrets", total));
+
     Ok(())
 }
@@ -1,95 +1,180 @@
 //! Lock and unlock commands
 
+use crate::cli_error::CliError;
 use crate::session::{self, cache};
 use crate::utils::{display, input};
-use crate::vault::Vault;
+use crate::vault::{Vault, VaultError};
+use std::time::Duration;
 
 /// Runs the lock command
-pub fn lock() -> Result<(), Box<dyn std::error::Error>> {
+pub fn lock() -> Result<(), CliError> {
     cache::clear_session()?;
+    cache::set_locked(true)?;
     display::success("Vault locked. Master password will be required for next access.");
     Ok(())
 }
 
 /// Runs the unlock command
-pub fn unlock(timeout: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `print_remaining` just reports the minutes left on the current session
+/// and returns, ignoring every other flag. `extend` re-saves the session to
+/// reset its timer without prompting, as long as a valid cached key already
+/// exists; otherwise it falls through to the normal password prompt below.
+///
+/// Authentication failure returns `Err(CliError::AuthFailed)`, a stable
+/// nonzero exit code (3, see `CliError::exit_code`) scripts can branch on
+/// rather than matching stderr text. The success message is suppressed by
+/// the global `-q`/`--quiet` flag like every other `display::success` call
+/// - there's no separate `--quiet` on this subcommand.
+pub fn unlock(timeout: Option<u32>, extend: bool, print_remaining: bool) -> Result<(), CliError> {
+    if print_remaining {
+        match cache::session_remaining_minutes() {
+            Some(mins) => display::info(&format!("Session expires in {} minute{}.", mins, if mins == 1 { "" } else { "s" })),
+            None => display::info("No active session."),
+        }
+        return Ok(());
+    }
+
     let mut vault = Vault::new();
-    
+
     if !vault.exists() {
-        display::error("Vault not initialized. Run 'kookie init' first.");
-        return Ok(());
+        return Err(CliError::VaultMissing(
+            "Vault not initialized. Run 'kookie init' first.".to_string(),
+        ));
     }
-    
+
     // Get timeout from config or argument
     let config = cache::load_config();
+    vault.set_storage_backend(config.storage_backend);
+    vault.set_normalize_names(config.normalize_names);
     let timeout_minutes = timeout.unwrap_or(config.timeout_minutes);
-    
+
+    if extend {
+        if let Some(key) = cache::get_cached_key() {
+            session::save_session(&key, timeout_minutes)?;
+            display::success(&format!("Session extended for {} minutes.", timeout_minutes));
+            return Ok(());
+        }
+        display::info("No active session to extend; prompting for the master password instead.");
+    }
+
     // Check if already unlocked
-    if let Some(_key) = cache::get_cached_key() {
-        display::info("Vault is already unlocked.");
-        return Ok(());
+    if !extend {
+        if let Some(_key) = cache::get_cached_key() {
+            display::info("Vault is already unlocked.");
+            return Ok(());
+        }
+    }
+
+    // Prompt for password, retrying on a wrong guess
+    unlock_with_retries(&mut vault, config.unlock_attempts)?;
+
+    // An explicit unlock always clears the "locked" sentinel
+    cache::set_locked(false)?;
+
+    // Save session
+    if timeout_minutes > 0 {
+        let key = vault.key().ok_or("Vault unlocked but key missing")?;
+        session::save_session(&key, timeout_minutes)?;
+
+        display::success(&format!("Vault unlocked for {} minutes.", timeout_minutes));
+    } else {
+        display::success("Vault unlocked (session disabled).");
     }
-    
-    // Prompt for password
-    let password = input::prompt_password("Enter master password:")?;
-    
-    // Try to unlock
-    match vault.unlock(&password) {
-        Ok(()) => {
-            // Save session
-            if timeout_minutes > 0 {
-                // We need to get the key from the vault - but it's private
-                // So we'll re-derive it here
-                let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-                let key = crate::crypto::kdf::derive_key(&password, &vault_file.salt)?;
-                session::save_session(&key, timeout_minutes)?;
-                
-                display::success(&format!(
-                    "Vault unlocked for {} minutes.",
-                    timeout_minutes
+    Ok(())
+}
+
+/// Prompts for the master password and attempts to unlock `vault`, retrying
+/// on a wrong password up to `max_attempts` times with an increasing delay
+/// between attempts (1s, 2s, ...) to slow down brute-force guessing. Shared
+/// by `unlock` and `ensure_unlocked` so both give up the same way: a clear
+/// error (mapped to a non-zero exit code) once the attempts run out.
+fn unlock_with_retries(vault: &mut Vault, max_attempts: u32) -> Result<(), CliError> {
+    let max_attempts = max_attempts.max(1);
+
+    for attempt in 1..=max_attempts {
+        let password = input::prompt_password("Enter master password:")?;
+        match vault.unlock(&password) {
+            Ok(()) => return Ok(()),
+            Err(VaultError::WrongPassword) if attempt < max_attempts => {
+                let delay = Duration::from_secs(attempt as u64);
+                display::warning(&format!(
+                    "Wrong password ({}/{} attempts). Retrying in {}s...",
+                    attempt, max_attempts, delay.as_secs()
                 ));
-            } else {
-                display::success("Vault unlocked (session disabled).");
+                if let Some(hint) = vault.hint() {
+                    display::info(&format!("Hint: {}", hint));
+                }
+                std::thread::sleep(delay);
             }
-            Ok(())
-        }
-        Err(e) => {
-            display::error(&format!("Failed to unlock: {}", e));
-            Ok(())
+            Err(VaultError::WrongPassword) => {
+                if let Some(hint) = vault.hint() {
+                    display::info(&format!("Hint: {}", hint));
+                }
+                return Err(CliError::AuthFailed(format!(
+                    "Failed to unlock after {} attempt{}: {}",
+                    attempt,
+                    if attempt == 1 { "" } else { "s" },
+                    VaultError::WrongPassword
+                )));
+            }
+            Err(e) => return Err(e.into()),
         }
     }
+
+    unreachable!("loop always returns on its last iteration")
 }
 
 /// Ensures the vault is unlocked, prompting for password if needed
 /// Returns the unlocked vault
-pub fn ensure_unlocked() -> Result<Vault, Box<dyn std::error::Error>> {
+pub fn ensure_unlocked() -> Result<Vault, CliError> {
     let mut vault = Vault::new();
-    
+    let config = cache::load_config();
+    vault.set_storage_backend(config.storage_backend);
+    vault.set_normalize_names(config.normalize_names);
+
     if !vault.exists() {
-        return Err("Vault not initialized. Run 'kookie init' first.".into());
+        return Err(CliError::VaultMissing(
+            "Vault not initialized. Run 'kookie init' first.".to_string(),
+        ));
     }
-    
-    // Check for cached session
-    if let Some(key) = cache::get_cached_key() {
-        // Load vault with cached key
-        let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-        let decrypted = crate::crypto::decrypt(&key, &vault_file.encrypted_data)
-            .map_err(|_| "Session expired or corrupted. Please unlock again.")?;
-        vault.data = serde_json::from_slice(&decrypted)?;
-        return Ok(vault);
+
+    // Check for cached session, unless the vault was explicitly locked via
+    // `kookie lock` - that stays in effect regardless of timeout settings.
+    //
+    // NOTE: this fast path only reads the monolithic `vault.json` blob, so
+    // under `StorageBackend::PerSecretFile` a cached session won't see the
+    // individual secret files until the next full `vault.unlock()` (e.g.
+    // after the session expires). Fixing that means giving this path a way
+    // to set `vault`'s key so it can go through `read_data()` instead of
+    // decrypting inline, which is a bigger change than this fits.
+    if !cache::is_locked() {
+        if let Some(key) = cache::get_cached_key() {
+            // Load vault with cached key
+            let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
+            let decrypted = crate::crypto::decrypt(&key, &vault_file.encrypted_data).map_err(|_| {
+                CliError::AuthFailed("Session expired or corrupted. Please unlock again.".to_string())
+            })?;
+            vault.data = serde_json::from_slice(&decrypted)?;
+            return Ok(vault);
+        }
     }
-    
-    // Prompt for password
-    let password = input::prompt_password("Enter master password:")?;
-    vault.unlock(&password)?;
-    
+
+    // Prompt for password, retrying on a wrong guess
+    unlock_with_retries(&mut vault, config.unlock_attempts)?;
+
+    // A successful password entry clears the explicit lock
+    cache::set_locked(false)?;
+
     // Save session for convenience
-    let config = cache::load_config();
     if config.timeout_minutes > 0 {
-        let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-        let key = crate::crypto::kdf::derive_key(&password, &vault_file.salt)?;
-        session::save_session(&key, config.timeout_minutes)?;
+        let key = vault.key().ok_or("Vault unlocked but key missing")?;
+        cache::save_session_best_effort(&key, config.timeout_minutes)?;
     }
-    
+
+    // Best-effort: an expired trash entry missing its purge this time just
+    // gets caught on the next unlock, so a failure here shouldn't block it.
+    let _ = vault.purge_expired_trash(config.trash_retention_days);
+
     Ok(vault)
 }
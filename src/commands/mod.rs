@@ -1,11 +1,30 @@
 //! CLI command implementations
 
 pub mod add;
+pub mod attach;
+pub mod bench_kdf;
+pub mod burn;
+pub mod changelog;
+pub mod compact;
 pub mod config;
+pub mod copy;
 pub mod delete;
+pub mod doctor;
+pub mod exec;
+pub mod export;
+pub mod favorite;
 pub mod generate;
 pub mod get;
+pub mod git;
+pub mod import;
 pub mod init;
 pub mod install;
 pub mod list;
 pub mod lock;
+pub mod pin;
+pub mod recent;
+pub mod recovery;
+pub mod serve_once;
+pub mod status;
+pub mod sync;
+pub mod trash;
@@ -0,0 +1,65 @@
+//! Set-pin and PIN-based quick-unlock commands
+
+use crate::cli_error::CliError;
+use crate::crypto::kdf::KdfProfile;
+use crate::crypto::{self, kdf};
+use crate::session::{self, cache};
+use crate::utils::{display, input};
+
+/// Runs the set-pin command: wraps the current session's key with a
+/// PIN-derived key and stores it, so `unlock --pin` can reactivate the same
+/// session later without the master password. Requires an active session -
+/// there is no cold-vault path through a PIN.
+pub fn set() -> Result<(), CliError> {
+    let key = cache::get_cached_key().ok_or_else(|| {
+        CliError::AuthFailed("No active session. Run 'kookie unlock' first.".to_string())
+    })?;
+    let expires_at = cache::session_expiry().ok_or_else(|| {
+        CliError::AuthFailed("No active session. Run 'kookie unlock' first.".to_string())
+    })?;
+
+    let pin = input::prompt_password_confirmed("Set a PIN:")?;
+
+    let pin_salt = kdf::generate_salt();
+    // A PIN is a handful of digits, not a password - it still has to go
+    // through the full memory-hard cost, or an attacker who gets the
+    // .pin_session file can brute-force it offline in well under a second.
+    let pin_key = kdf::derive_key_with_profile(&pin, &pin_salt, KdfProfile::Standard)
+        .map_err(|e| CliError::Other(e.to_string()))?;
+    let wrapped_key = crypto::encrypt(&pin_key, &key).map_err(|e| CliError::Other(e.to_string()))?;
+
+    cache::save_pin_session(pin_salt, wrapped_key, expires_at)?;
+    display::success("PIN set. Use 'kookie unlock --pin' to quickly reactivate this session.");
+    Ok(())
+}
+
+/// Runs `unlock --pin`: recovers the session key from the stored PIN-wrapped
+/// session and reactivates it. Never falls back to a master password prompt -
+/// if there's no PIN session, or the PIN is wrong, this just fails.
+pub fn unlock_with_pin(timeout: Option<u32>) -> Result<(), CliError> {
+    let pin_session = cache::load_pin_session().ok_or_else(|| {
+        CliError::AuthFailed(
+            "No active PIN session. Run 'kookie unlock' with your master password, then 'kookie set-pin'."
+                .to_string(),
+        )
+    })?;
+
+    let pin = input::prompt_password("Enter PIN:")?;
+    let pin_key = kdf::derive_key_with_profile(&pin, &pin_session.pin_salt, KdfProfile::Standard)
+        .map_err(|e| CliError::Other(e.to_string()))?;
+    let decrypted = crypto::decrypt(&pin_key, &pin_session.wrapped_key)
+        .map_err(|_| CliError::AuthFailed("Wrong PIN.".to_string()))?;
+
+    if decrypted.len() != 32 {
+        return Err(CliError::AuthFailed("Wrong PIN.".to_string()));
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&decrypted);
+
+    let timeout_minutes = timeout.unwrap_or_else(|| cache::load_config().timeout_minutes);
+    session::save_session(&key, timeout_minutes)?;
+    cache::set_locked(false)?;
+
+    display::success("Vault unlocked with PIN.");
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! Recent secrets command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+
+/// Runs the recent command, listing the `limit` most recently created
+/// secrets across all types
+pub fn run(limit: usize) -> Result<(), crate::cli_error::CliError> {
+    let vault = ensure_unlocked()?;
+
+    let mut entries = vault.data.all_entries();
+
+    if entries.is_empty() {
+        display::info("No secrets found. Use 'kookie add' to add secrets.");
+        return Ok(());
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+
+    println!();
+    display::info(&format!("Most recently added ({}):", limit.min(entries.len())));
+    println!();
+
+    for entry in entries.iter().take(limit) {
+        let extra = format!(
+            "{}{}",
+            entry.secret_type,
+            entry.extra.as_deref().map(|e| format!(", {}", e)).unwrap_or_default()
+        );
+        display::list_item(&entry.id, &entry.name, Some(&extra), entry.favorite, false);
+    }
+
+    Ok(())
+}
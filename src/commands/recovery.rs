@@ -0,0 +1,84 @@
+//! Shamir secret-sharing recovery for the vault's master key
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::crypto::shamir;
+use crate::session::{self, cache};
+use crate::utils::{display, input};
+use crate::vault::Vault;
+
+/// Splits the unlocked vault's key into `shares` shares, any `threshold`
+/// of which can reconstruct it. Shares are only ever printed to stdout -
+/// never written to disk - so distributing them to separate holders is the
+/// caller's responsibility.
+pub fn split(shares: u8, threshold: u8) -> Result<(), CliError> {
+    let vault = ensure_unlocked()?;
+    let key = vault.key().ok_or("Vault unlocked but key missing")?;
+
+    let encoded_shares = shamir::split_key(&key, threshold, shares)?;
+
+    display::warning(
+        "Each share below is enough, together with the others, to reconstruct the vault key. \
+         Distribute them to separate people; none of these are saved anywhere by kookie.",
+    );
+    println!();
+    for (i, share) in encoded_shares.iter().enumerate() {
+        println!("Share {}/{}: {}", i + 1, encoded_shares.len(), share);
+    }
+    println!();
+    display::info(&format!(
+        "Any {} of these {} shares can reconstruct the vault key.",
+        threshold,
+        encoded_shares.len()
+    ));
+
+    Ok(())
+}
+
+/// Reconstructs the vault key from pasted shares and caches a session,
+/// without ever requiring (or accepting) the master password.
+pub fn combine() -> Result<(), CliError> {
+    let vault = Vault::new();
+    if !vault.exists() {
+        return Err(CliError::VaultMissing(
+            "Vault not initialized. Run 'kookie init' first.".to_string(),
+        ));
+    }
+
+    display::info("Paste shares one at a time. Submit a blank line when done.");
+    let mut shares = Vec::new();
+    loop {
+        let line = input::prompt_text(&format!("Share {}:", shares.len() + 1))?;
+        if line.is_empty() {
+            break;
+        }
+        shares.push(line);
+    }
+
+    if shares.len() < 2 {
+        return Err(CliError::Other(
+            "Need at least 2 shares to attempt recovery.".to_string(),
+        ));
+    }
+
+    let key = shamir::combine_shares(&shares)?;
+
+    // Recovery from too few (or mismatched) shares doesn't error on its
+    // own - it just reconstructs the wrong bytes - so verify the key
+    // actually decrypts this vault before trusting and caching it.
+    let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
+    crate::crypto::decrypt(&key, &vault_file.encrypted_data).map_err(|_| {
+        CliError::AuthFailed(
+            "Recovered key doesn't match this vault (wrong or too few shares).".to_string(),
+        )
+    })?;
+
+    let config = cache::load_config();
+    if config.timeout_minutes > 0 {
+        session::save_session(&key, config.timeout_minutes)?;
+    }
+    cache::set_locked(false)?;
+
+    display::success("Key reconstructed and verified; vault session cached.");
+    Ok(())
+}
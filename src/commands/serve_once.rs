@@ -0,0 +1,123 @@
+//! Write a secret to a named pipe exactly once
+//!
+//! `kookie serve-once <name>` is for init scripts and similar tools that
+//! read a credential from a FIFO rather than an environment variable or a
+//! file on disk, e.g.:
+//!
+//! ```bash
+//! mkfifo /run/secrets/db-password
+//! kookie serve-once db-password --out /run/secrets/db-password &
+//! some-init-script-that-reads-the-fifo
+//! ```
+//!
+//! Security considerations: kookie does not create the pipe or set its
+//! permissions - `mkfifo` it with whatever mode/ownership keeps other
+//! users on the box from opening it for reading before your intended
+//! reader does. Opening a FIFO for writing blocks until something opens
+//! the read end, so a reader that never shows up hangs this command
+//! forever by design; pass `--timeout-secs` to give up instead of
+//! blocking indefinitely. Once written, the value also briefly exists in
+//! the kernel's pipe buffer, same as any other FIFO use.
+
+use crate::cli_error::CliError;
+use crate::commands::get::resolve_id_or_name;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use crate::vault::Vault;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Returns a secret's primary value by name, the same lookup `kookie exec`
+/// uses for its `--env` mappings.
+fn secret_value(vault: &Vault, id_or_name: &str) -> Option<String> {
+    if let Some(p) = vault.get_password(id_or_name) {
+        return Some(p.password.clone());
+    }
+    if let Some(k) = vault.get_api_key(id_or_name) {
+        return Some(k.key.clone());
+    }
+    if let Some(n) = vault.get_note(id_or_name) {
+        return Some(n.content.clone());
+    }
+    if let Some(c) = vault.get_db_credential(id_or_name) {
+        return Some(c.connection_string());
+    }
+    if let Some(t) = vault.get_token(id_or_name) {
+        return Some(t.token.clone());
+    }
+    if let Some(s) = vault.get_ssh_key(id_or_name) {
+        return Some(s.private_key.clone());
+    }
+    None
+}
+
+#[cfg(unix)]
+fn ensure_is_fifo(path: &Path) -> Result<(), CliError> {
+    use std::os::unix::fs::FileTypeExt;
+    let file_type = std::fs::metadata(path)
+        .map_err(|e| CliError::Other(format!("Can't stat '{}': {}", path.display(), e)))?
+        .file_type();
+    if !file_type.is_fifo() {
+        return Err(CliError::Other(format!(
+            "'{}' is not a named pipe. Create one first with `mkfifo {}`.",
+            path.display(),
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn ensure_is_fifo(_path: &Path) -> Result<(), CliError> {
+    Err(CliError::Other(
+        "Named pipes are a Unix concept; 'serve-once' isn't supported on this platform.".to_string(),
+    ))
+}
+
+/// Opens `path` for writing, which blocks until a reader opens the other
+/// end of the FIFO. Without `timeout_secs` this can block indefinitely -
+/// that's how a FIFO hands a value to whatever reads it next.
+fn open_for_write(path: &Path, timeout_secs: Option<u64>) -> Result<std::fs::File, CliError> {
+    let Some(secs) = timeout_secs else {
+        return std::fs::OpenOptions::new().write(true).open(path).map_err(CliError::from);
+    };
+
+    let owned_path = path.to_path_buf();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(std::fs::OpenOptions::new().write(true).open(&owned_path));
+    });
+
+    match rx.recv_timeout(Duration::from_secs(secs)) {
+        Ok(opened) => opened.map_err(CliError::from),
+        Err(_) => Err(CliError::Other(format!(
+            "Timed out after {}s waiting for a reader to open '{}'.",
+            secs,
+            path.display()
+        ))),
+    }
+}
+
+/// Runs `kookie serve-once <name>`: unlocks the vault, resolves the
+/// secret's primary value, and writes it to `path` - an existing FIFO -
+/// exactly once with no trailing newline or masking, then exits.
+/// `timeout_secs`, if given, gives up waiting for a reader instead of
+/// blocking forever.
+pub fn run(id_or_name: &str, path: &Path, timeout_secs: Option<u64>) -> Result<(), CliError> {
+    let vault = ensure_unlocked()?;
+    let resolved = resolve_id_or_name(&vault, id_or_name)?;
+
+    let value = secret_value(&vault, &resolved)
+        .ok_or_else(|| CliError::NotFound(format!("Secret '{}' not found.", id_or_name)))?;
+
+    ensure_is_fifo(path)?;
+
+    let mut file = open_for_write(path, timeout_secs)?;
+    file.write_all(value.as_bytes())?;
+    file.flush()?;
+
+    display::success(&format!("Wrote secret to '{}'.", path.display()));
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Vault and environment status command
+
+use crate::session::cache;
+use crate::utils::{clipboard, display};
+use crate::vault::Vault;
+
+/// Runs the status command
+pub fn run() -> Result<(), crate::cli_error::CliError> {
+    let vault = Vault::new();
+
+    println!();
+    display::info("Kookie status:");
+
+    if vault.exists() {
+        display::success(&format!("Vault: {}", vault.path.display()));
+    } else {
+        display::warning("Vault: not initialized (run 'kookie init')");
+    }
+
+    if cache::is_locked() {
+        display::info("Session: locked (explicit lock)");
+    } else if cache::get_cached_key().is_some() {
+        display::success("Session: unlocked");
+    } else {
+        display::info("Session: locked");
+    }
+
+    if clipboard::is_available() {
+        display::success("Clipboard: available");
+    } else {
+        display::warning("Clipboard: unavailable (use --show to print values instead of --copy)");
+    }
+
+    if let Some(due) = passwords_due_for_rotation(&vault) {
+        if due > 0 {
+            display::warning(&format!(
+                "Passwords: {} due for rotation (see 'kookie list --passwords')",
+                due
+            ));
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Counts passwords past their `rotate_after_days` interval, using only a
+/// cached session - never prompts for a password, so this stays silent
+/// (returns `None`) if the vault is locked.
+fn passwords_due_for_rotation(vault: &Vault) -> Option<usize> {
+    if cache::is_locked() {
+        return None;
+    }
+    let key = cache::get_cached_key()?;
+    let vault_file = crate::vault::storage::load_vault_file(&vault.path).ok()?;
+    let decrypted = crate::crypto::decrypt(&key, &vault_file.encrypted_data).ok()?;
+    let data: crate::vault::VaultData = serde_json::from_slice(&decrypted).ok()?;
+    Some(data.passwords.iter().filter(|p| p.is_due_for_rotation()).count())
+}
@@ -0,0 +1,191 @@
+//! Remote sync of the encrypted vault file
+//!
+//! `sync push`/`sync pull` upload/download `vault.json` exactly as stored
+//! on disk, still encrypted - the remote, whether a WebDAV server or an
+//! S3-compatible endpoint reachable over plain HTTP PUT/GET, never sees
+//! plaintext secrets. Authentication is HTTP Basic auth; S3-compatible
+//! endpoints need to be fronted by something that accepts that (a
+//! presigned URL or a gateway) since kookie doesn't implement SigV4
+//! signing.
+//!
+//! Requires the `sync` feature; without it, `push`/`pull` return a clear
+//! error telling the user to rebuild with it rather than silently no-op'ing.
+
+use crate::cli_error::CliError;
+use crate::session::cache;
+use crate::utils::display;
+use crate::vault::storage;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha2::{Digest, Sha256};
+
+/// Stores the remote to sync against. Credentials are stored in
+/// `config.json` in plaintext, same as `clipboard_command`.
+///
+/// Refuses a non-`https://` URL unless `allow_insecure_http` is set - Basic
+/// auth credentials go out in every `push`/`pull` request, and a plain
+/// `http://` remote sends them unencrypted.
+pub fn set_remote(
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+    allow_insecure_http: bool,
+) -> Result<(), CliError> {
+    if !url.starts_with("https://") && !allow_insecure_http {
+        return Err(CliError::Other(format!(
+            "'{}' is not an https:// URL; Basic auth credentials would be sent in \
+             plaintext. Use an https:// remote, or pass --allow-insecure-http to \
+             configure it anyway (e.g. for local testing).",
+            url
+        )));
+    }
+
+    let mut config = cache::load_config();
+    config.sync_remote_url = Some(url);
+    config.sync_remote_username = username;
+    config.sync_remote_password = password;
+    cache::save_config(&config)?;
+    display::success("Sync remote configured.");
+    Ok(())
+}
+
+/// Uploads the local `vault.json` to the configured remote, overwriting
+/// whatever is there.
+pub fn push() -> Result<(), CliError> {
+    let mut config = cache::load_config();
+    let url = config
+        .sync_remote_url
+        .clone()
+        .ok_or_else(|| CliError::Other("No sync remote configured. Run 'kookie sync set-remote --url <url>' first.".to_string()))?;
+
+    let bytes = std::fs::read(storage::get_vault_path())?;
+    let local_hash = hash_of(&bytes);
+
+    let etag = sync_backend::put(
+        &url,
+        config.sync_remote_username.as_deref(),
+        config.sync_remote_password.as_deref(),
+        &bytes,
+    )?;
+
+    config.sync_last_local_hash = Some(local_hash);
+    config.sync_last_remote_etag = etag;
+    cache::save_config(&config)?;
+
+    display::success("Vault pushed to remote.");
+    Ok(())
+}
+
+/// Downloads the remote vault file and replaces the local one, unless
+/// both the local and remote files changed since the last successful
+/// `push`/`pull` - in which case this refuses rather than clobbering
+/// whichever side lost the race.
+pub fn pull() -> Result<(), CliError> {
+    let mut config = cache::load_config();
+    let url = config
+        .sync_remote_url
+        .clone()
+        .ok_or_else(|| CliError::Other("No sync remote configured. Run 'kookie sync set-remote --url <url>' first.".to_string()))?;
+
+    let local_bytes = std::fs::read(storage::get_vault_path())?;
+    let local_hash = hash_of(&local_bytes);
+    let local_changed = config.sync_last_local_hash.as_deref().is_some_and(|h| h != local_hash);
+
+    let (remote_bytes, remote_etag) = sync_backend::get(
+        &url,
+        config.sync_remote_username.as_deref(),
+        config.sync_remote_password.as_deref(),
+    )?;
+    // If either side has no ETag to compare - the server didn't send one, or
+    // we've never successfully synced - there's no reliable way to tell
+    // "unchanged" from "changed", so assume changed rather than risk
+    // silently skipping a real update.
+    let remote_changed = match (&config.sync_last_remote_etag, &remote_etag) {
+        (Some(last), Some(current)) => last != current,
+        _ => true,
+    };
+
+    if !remote_changed {
+        display::info("Already up to date.");
+        return Ok(());
+    }
+
+    if local_changed {
+        return Err(CliError::Other(
+            "Local and remote vault files both changed since the last sync; refusing to overwrite. \
+             Push the local changes first, or back up vault.json before pulling.".to_string(),
+        ));
+    }
+
+    std::fs::write(storage::get_vault_path(), &remote_bytes)?;
+
+    config.sync_last_local_hash = Some(hash_of(&remote_bytes));
+    config.sync_last_remote_etag = remote_etag;
+    cache::save_config(&config)?;
+
+    display::success("Vault pulled from remote.");
+    Ok(())
+}
+
+fn hash_of(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    BASE64.encode(hasher.finalize())
+}
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync_backend {
+    use crate::cli_error::CliError;
+
+    fn basic_auth(req: reqwest::blocking::RequestBuilder, username: Option<&str>, password: Option<&str>) -> reqwest::blocking::RequestBuilder {
+        match username {
+            Some(user) => req.basic_auth(user, password),
+            None => req,
+        }
+    }
+
+    pub fn put(url: &str, username: Option<&str>, password: Option<&str>, body: &[u8]) -> Result<Option<String>, CliError> {
+        let client = reqwest::blocking::Client::new();
+        let req = basic_auth(client.put(url), username, password).body(body.to_vec());
+        let resp = req.send().map_err(|e| CliError::Other(format!("Sync push failed: {}", e)))?;
+        if !resp.status().is_success() {
+            return Err(CliError::Other(format!("Remote rejected the push: HTTP {}", resp.status())));
+        }
+        Ok(etag_of(&resp))
+    }
+
+    pub fn get(url: &str, username: Option<&str>, password: Option<&str>) -> Result<(Vec<u8>, Option<String>), CliError> {
+        let client = reqwest::blocking::Client::new();
+        let req = basic_auth(client.get(url), username, password);
+        let resp = req.send().map_err(|e| CliError::Other(format!("Sync pull failed: {}", e)))?;
+        if !resp.status().is_success() {
+            return Err(CliError::Other(format!("Remote rejected the pull: HTTP {}", resp.status())));
+        }
+        let etag = etag_of(&resp);
+        let bytes = resp.bytes().map_err(|e| CliError::Other(format!("Sync pull failed: {}", e)))?.to_vec();
+        Ok((bytes, etag))
+    }
+
+    /// `None` means the server didn't send an `ETag` header at all, as
+    /// opposed to sending an empty one - callers must not treat that as a
+    /// value to compare against, or every sync looks unchanged forever.
+    fn etag_of(resp: &reqwest::blocking::Response) -> Option<String> {
+        resp.headers().get("etag").and_then(|v| v.to_str().ok()).map(str::to_string)
+    }
+}
+
+#[cfg(not(feature = "sync"))]
+pub(crate) mod sync_backend {
+    use crate::cli_error::CliError;
+
+    fn disabled() -> CliError {
+        CliError::Other("Remote sync requires the 'sync' feature; rebuild with `--features sync`.".to_string())
+    }
+
+    pub fn put(_url: &str, _username: Option<&str>, _password: Option<&str>, _body: &[u8]) -> Result<Option<String>, CliError> {
+        Err(disabled())
+    }
+
+    pub fn get(_url: &str, _username: Option<&str>, _password: Option<&str>) -> Result<(Vec<u8>, Option<String>), CliError> {
+        Err(disabled())
+    }
+}
@@ -0,0 +1,39 @@
+//! Restore and empty-trash commands
+
+use crate::cli_error::CliError;
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::{display, input};
+
+/// Runs the restore command: moves a trashed secret back into its original
+/// collection.
+pub fn restore(id_or_name: &str) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+    let name = vault.restore(id_or_name)?;
+    display::success(&format!("Restored '{}' from the trash.", name));
+    Ok(())
+}
+
+/// Runs the empty-trash command: permanently removes everything in the trash.
+pub fn empty(force: bool) -> Result<(), CliError> {
+    let mut vault = ensure_unlocked()?;
+
+    if vault.data.trash.is_empty() {
+        display::info("Trash is already empty.");
+        return Ok(());
+    }
+
+    if !force {
+        display::warning(&format!(
+            "You are about to permanently delete {} trashed secret(s).",
+            vault.data.trash.len()
+        ));
+        if !input::prompt_confirm("Are you sure?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let count = vault.empty_trash()?;
+    display::success(&format!("Permanently removed {} secret(s) from the trash.", count));
+    Ok(())
+}
@@ -20,6 +20,12 @@ pub enum CipherError {
     DecryptionFailed,
     #[error("Invalid ciphertext format")]
     InvalidFormat,
+    /// The ciphertext decoded to a plausible length but its GCM
+    /// authentication tag didn't verify - either the wrong key was used, or
+    /// the ciphertext was tampered with/corrupted in transit. AES-GCM can't
+    /// distinguish those two causes from this error alone.
+    #[error("Authentication failed - wrong password, or the data was tampered with or corrupted")]
+    AuthenticationFailed,
 }
 
 /// Nonce size for AES-GCM (96 bits = 12 bytes)
@@ -78,7 +84,7 @@ pub fn decrypt(key: &[u8; 32], ciphertext_b64: &str) -> Result<Vec<u8>, CipherEr
 
     cipher
         .decrypt(nonce, ciphertext)
-        .map_err(|_| CipherError::DecryptionFailed)
+        .map_err(|_| CipherError::AuthenticationFailed)
 }
 
 #[cfg(test)]
@@ -105,7 +111,46 @@ mod tests {
         let encrypted = encrypt(&key1, plaintext).unwrap();
         let result = decrypt(&key2, &encrypted);
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_authentication_failed() {
+        let key = [0x42u8; 32];
+        let plaintext = b"Secret data";
+
+        let encrypted = encrypt(&key, plaintext).unwrap();
+        let mut combined = BASE64.decode(&encrypted).unwrap();
+        let last = combined.len() - 1;
+        combined[last] ^= 0xFF;
+        let tampered = BASE64.encode(&combined);
+
+        let result = decrypt(&key, &tampered);
+
+        assert!(matches!(result, Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_truncated_ciphertext_is_invalid_format() {
+        let key = [0x42u8; 32];
+        let plaintext = b"Secret data";
+
+        let encrypted = encrypt(&key, plaintext).unwrap();
+        let combined = BASE64.decode(&encrypted).unwrap();
+        let truncated = BASE64.encode(&combined[..NONCE_SIZE - 1]);
+
+        let result = decrypt(&key, &truncated);
+
+        assert!(matches!(result, Err(CipherError::InvalidFormat)));
+    }
+
+    #[test]
+    fn test_malformed_base64_is_invalid_format() {
+        let key = [0x42u8; 32];
+
+        let result = decrypt(&key, "not-valid-base64!!!");
+
+        assert!(matches!(result, Err(CipherError::InvalidFormat)));
     }
 
     #[test]
@@ -0,0 +1,71 @@
+//! Key commitment check
+//!
+//! AES-GCM is not key-committing: for certain maliciously crafted
+//! ciphertexts, more than one key can produce a valid authentication tag
+//! (a "partitioning oracle" style attack). That's academic for a local
+//! vault file nobody else can tamper with, but it also means a wrong
+//! password is only ever detected indirectly, via a failed GCM tag check
+//! after a full decrypt attempt.
+//!
+//! This module derives a small, unencrypted HMAC "verifier" from the
+//! derived key and a fixed context string, stored alongside the vault.
+//! Checking it before decrypting gives an immediate, unambiguous "wrong
+//! password" without relying on AES-GCM's authentication tag to carry that
+//! meaning too.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Fixed context string the HMAC is computed over. Any constant works -
+/// the verifier only needs to be reproducible from the same key, not
+/// secret-specific.
+const CONTEXT: &[u8] = b"kookie-vault-key-check-v1";
+
+/// Computes the base64-encoded key-check verifier for `key`, to be stored
+/// in `VaultFile::key_check`.
+pub fn compute(key: &[u8; 32]) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(CONTEXT);
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+/// Checks whether `key` produces the given verifier, in constant time.
+/// Returns `false` (rather than erroring) on a malformed `expected_b64`,
+/// since that's indistinguishable from "wrong key" to the caller.
+pub fn verify(key: &[u8; 32], expected_b64: &str) -> bool {
+    let Ok(expected) = BASE64.decode(expected_b64) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(CONTEXT);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_accepts_matching_key() {
+        let key = [0x42u8; 32];
+        let check = compute(&key);
+        assert!(verify(&key, &check));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_key() {
+        let key = [0x42u8; 32];
+        let other = [0x43u8; 32];
+        let check = compute(&key);
+        assert!(!verify(&other, &check));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_base64() {
+        let key = [0x42u8; 32];
+        assert!(!verify(&key, "not-valid-base64!!!"));
+    }
+}
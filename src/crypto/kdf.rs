@@ -9,6 +9,7 @@ use argon2::{
     Argon2, Params, Version,
 };
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Key derivation errors
@@ -30,7 +31,49 @@ const TIME_COST: u32 = 3;
 const PARALLELISM: u32 = 4;
 const OUTPUT_LEN: usize = 32;
 
-/// Derives a 256-bit encryption key from a password using Argon2id
+/// Lightweight parameters used by [`KdfProfile::Fast`], intended for CI and
+/// test vaults where the usual memory-hard cost would slow every run.
+const FAST_MEMORY_COST: u32 = 8192; // 8 MB
+const FAST_TIME_COST: u32 = 1;
+const FAST_PARALLELISM: u32 = 1;
+
+/// Selects which Argon2id cost parameters to use when deriving a key.
+///
+/// The profile used at `init` time is stored alongside the vault so that
+/// later unlocks derive the key with matching parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KdfProfile {
+    /// The default, memory-hard cost suitable for protecting real secrets.
+    #[default]
+    Standard,
+    /// Much cheaper cost for CI/scripted vaults where speed matters more
+    /// than brute-force resistance. Never used unless explicitly requested.
+    Fast,
+}
+
+impl KdfProfile {
+    fn costs(&self) -> (u32, u32, u32) {
+        match self {
+            KdfProfile::Standard => (MEMORY_COST, TIME_COST, PARALLELISM),
+            KdfProfile::Fast => (FAST_MEMORY_COST, FAST_TIME_COST, FAST_PARALLELISM),
+        }
+    }
+}
+
+impl std::str::FromStr for KdfProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" | "default" => Ok(KdfProfile::Standard),
+            "fast" => Ok(KdfProfile::Fast),
+            other => Err(format!("Unknown KDF profile '{other}'. Valid options: standard, fast")),
+        }
+    }
+}
+
+/// Derives a 256-bit encryption key from a password using Argon2id with the
+/// standard cost parameters.
 ///
 /// # Arguments
 /// * `password` - The master password
@@ -38,10 +81,39 @@ const OUTPUT_LEN: usize = 32;
 ///
 /// # Returns
 /// A 32-byte (256-bit) key suitable for AES-256-GCM
+#[allow(dead_code)]
 pub fn derive_key(password: &str, salt: &str) -> Result<[u8; 32], KdfError> {
+    derive_key_with_profile(password, salt, KdfProfile::Standard)
+}
+
+/// Derives a 256-bit encryption key from a password using Argon2id, with the
+/// cost parameters selected by `profile`.
+pub fn derive_key_with_profile(
+    password: &str,
+    salt: &str,
+    profile: KdfProfile,
+) -> Result<[u8; 32], KdfError> {
+    let (memory_cost, time_cost, parallelism) = profile.costs();
+    let started = std::time::Instant::now();
+    let key = derive_key_with_params(password, salt, memory_cost, time_cost, parallelism)?;
+    crate::utils::display::verbose(&format!("KDF ({profile:?}) took {:.0?}", started.elapsed()));
+    Ok(key)
+}
+
+/// Derives a 256-bit encryption key from a password using Argon2id, with
+/// explicit cost parameters rather than a named profile. Used by
+/// `derive_key_with_profile` and by `kookie bench-kdf` to try out parameters
+/// before committing to them at `init` time.
+pub fn derive_key_with_params(
+    password: &str,
+    salt: &str,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+) -> Result<[u8; 32], KdfError> {
     let salt = SaltString::from_b64(salt).map_err(|_| KdfError::InvalidSalt)?;
 
-    let params = Params::new(MEMORY_COST, TIME_COST, PARALLELISM, Some(OUTPUT_LEN))
+    let params = Params::new(memory_cost, time_cost, parallelism, Some(OUTPUT_LEN))
         .map_err(|e| KdfError::DerivationError(e.to_string()))?;
 
     let argon2 = Argon2::new(argon2::Algorithm::Argon2id, Version::V0x13, params);
@@ -51,11 +123,11 @@ pub fn derive_key(password: &str, salt: &str) -> Result<[u8; 32], KdfError> {
         .map_err(|e| KdfError::DerivationError(e.to_string()))?;
 
     let hash_output = hash.hash.ok_or_else(|| KdfError::DerivationError("No hash output".into()))?;
-    
+
     let bytes = hash_output.as_bytes();
     let mut key = [0u8; 32];
     key.copy_from_slice(&bytes[..32]);
-    
+
     Ok(key)
 }
 
@@ -1,6 +1,8 @@
 //! Cryptography module for key derivation and encryption
 
 pub mod cipher;
+pub mod commitment;
 pub mod kdf;
+pub mod shamir;
 
 pub use cipher::{decrypt, encrypt};
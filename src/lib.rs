@@ -2,12 +2,44 @@
 //!
 //! This library provides the core functionality for managing encrypted secrets
 //! including passwords, API keys, notes, database credentials, and tokens.
+//!
+//! # Embedding kookie
+//!
+//! Everything the `kookie` CLI does goes through [`Vault`], which is safe to
+//! use directly from another Rust binary - the CLI's `commands` module is a
+//! thin wrapper over it that adds terminal prompts and stdout formatting.
+//!
+//! ```no_run
+//! use kookie::{Secret, SecretType, Vault};
+//! use kookie::vault::types::Note;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut vault = Vault::open("/path/to/vault.json", "master password")?;
+//!
+//! vault.add_secret(Secret::Note(Note::new(
+//!     "reminder".to_string(),
+//!     "buy milk".to_string(),
+//! )))?;
+//!
+//! for entry in vault.list_secrets(Some(SecretType::Note)) {
+//!     println!("{}: {}", entry.id, entry.name);
+//! }
+//!
+//! if let Some(secret) = vault.get_secret("reminder") {
+//!     println!("found a {}", secret.secret_type());
+//! }
+//!
+//! vault.delete_secret("reminder")?;
+//! # Ok(())
+//! # }
+//! ```
 
+pub mod cli_error;
 pub mod commands;
 pub mod crypto;
 pub mod session;
 pub mod utils;
 pub mod vault;
 
-pub use vault::types::SecretType;
+pub use vault::types::{Secret, SecretType};
 pub use vault::Vault;
@@ -41,12 +41,15 @@
 use clap::{Parser, Subcommand};
 use colored::*;
 
+mod cli_error;
 mod commands;
 mod crypto;
 mod session;
 mod utils;
 mod vault;
 
+use cli_error::CliError;
+
 /// 🍪 Kookie - A secure, local-first, encrypted secret manager for developers
 #[derive(Parser)]
 #[command(name = "kookie")]
@@ -55,6 +58,28 @@ mod vault;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Suppress non-essential output (errors still print); for scripting
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print diagnostic lines (files read, session expiry, KDF timing)
+    #[arg(short = 'v', long, global = true)]
+    verbose: bool,
+
+    /// Disable colored output (also honored via the NO_COLOR env var)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Vault directory to use, overriding KOOKIE_HOME/XDG_DATA_HOME
+    #[arg(long, global = true, value_name = "DIR")]
+    home: Option<std::path::PathBuf>,
+
+    /// Auto-confirm every `[y/N]`/`[Y/n]` prompt (delete, trash, import,
+    /// overwrite, ...) instead of blocking on stdin; for scripting and CI.
+    /// Each confirmed action still prints what it did.
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
 }
 
 #[derive(Subcommand)]
@@ -64,8 +89,29 @@ enum Commands {
         /// Force reinitialization (deletes existing vault)
         #[arg(short, long)]
         force: bool,
+
+        /// Read the master password from KOOKIE_MASTER_PASSWORD or stdin
+        /// instead of an interactive prompt (also auto-detected when stdin
+        /// is not a TTY, e.g. in CI)
+        #[arg(long)]
+        from_stdin_password: bool,
+
+        /// Argon2id cost profile to use: standard (default) or fast
+        #[arg(long)]
+        kdf_profile: Option<String>,
+
+        /// Restore a backup (see `kookie export`/`kookie import`) into the
+        /// vault immediately after initializing it. Fails cleanly, leaving
+        /// no vault behind, if the backup can't be read and decrypted.
+        #[arg(long)]
+        import: Option<std::path::PathBuf>,
+
+        /// If the backup at --import is age-encrypted, the age identity
+        /// file to decrypt it with
+        #[arg(long)]
+        age_identity: Option<std::path::PathBuf>,
     },
-    
+
     /// Lock the vault (clear session)
     Lock,
     
@@ -74,8 +120,27 @@ enum Commands {
         /// Timeout in minutes (overrides config)
         #[arg(short, long)]
         timeout: Option<u32>,
+
+        /// Reset the current session's timer without prompting, if one exists
+        #[arg(long)]
+        extend: bool,
+
+        /// Print the minutes remaining on the current session and exit
+        #[arg(long)]
+        print_remaining: bool,
+
+        /// Unlock with a short PIN instead of the master password, reactivating
+        /// an existing session set up with 'kookie set-pin'. Fails if there is
+        /// no active PIN session rather than falling back to a master password
+        /// prompt - it can never unlock a cold vault.
+        #[arg(long)]
+        pin: bool,
     },
-    
+
+    /// Set a short PIN that can reactivate the current session without the
+    /// master password, until the session itself expires
+    SetPin,
+
     /// Add a new secret
     Add {
         /// Add a password
@@ -97,8 +162,41 @@ enum Commands {
         /// Add a token
         #[arg(long, group = "secret_type")]
         token: bool,
+
+        /// Add a custom secret with user-defined fields
+        #[arg(long, group = "secret_type")]
+        custom: bool,
+
+        /// With --custom, prompt for exactly the fields defined by this
+        /// schema (loaded from <vault dir>/schemas/<name>.json) instead of
+        /// the free-form "enter fields one at a time" loop
+        #[arg(long, requires = "custom")]
+        schema: Option<String>,
+
+        /// Import an existing SSH key pair from ~/.ssh/<file> (and <file>.pub)
+        #[arg(long, group = "secret_type")]
+        ssh: bool,
+
+        /// Secret value for password/api-key/token, skipping the
+        /// interactive prompt. Use "@path" to read from a file or "-" to
+        /// read one line from stdin - passing the literal value here is
+        /// discouraged since it lands in shell history.
+        #[arg(long)]
+        value: Option<String>,
+
+        /// Re-prompt for the secret value and require it to match, to catch
+        /// typos when pasting blind. No effect when --value is given.
+        #[arg(long)]
+        confirm: bool,
+
+        /// Pre-seed the secret's name, skipping the interactive name prompt
+        /// (still validated for non-empty and name collisions). Useful for
+        /// scripted adds or when you already know the name and just want to
+        /// be prompted for the value.
+        #[arg(long)]
+        name: Option<String>,
     },
-    
+
     /// List stored secrets
     List {
         /// Show only passwords
@@ -120,28 +218,202 @@ enum Commands {
         /// Show only tokens
         #[arg(long)]
         tokens: bool,
+
+        /// Show only custom secrets
+        #[arg(long)]
+        custom: bool,
+
+        /// Show only SSH keys
+        #[arg(long)]
+        ssh: bool,
+
+        /// Show only favorited secrets (see 'kookie fav')
+        #[arg(long)]
+        favorites: bool,
+
+        /// Print every matching secret's value in full instead of a masked
+        /// summary, after an interactive confirmation. Refuses to run
+        /// non-interactively.
+        #[arg(long)]
+        reveal: bool,
+
+        /// Output format: "bullet" (default) or "table" for aligned columns
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Print only per-type counts (e.g. "passwords: 12, ..., total: 27") and exit
+        #[arg(long = "count-only")]
+        count_only: bool,
+
+        /// With --count-only, print a single JSON object instead of plain text
+        #[arg(long)]
+        json: bool,
+
+        /// With --db, show only credentials tagged with this environment
+        /// (e.g. "prod", "staging")
+        #[arg(long)]
+        env: Option<String>,
+
+        /// Print only matching names, one per line, with none of the usual
+        /// decoration - for scripting (e.g. `xargs -n1 kookie get`)
+        #[arg(long = "names-only")]
+        names_only: bool,
+
+        /// With --names-only, separate names with NUL instead of newline,
+        /// for `xargs -0` safety with names containing spaces
+        #[arg(long)]
+        null: bool,
+
+        /// Sort order within a type: "expiry" sorts --tokens so
+        /// already-expired come first (in red), then soonest-expiring,
+        /// then tokens with no expiry last. No effect on other types.
+        #[arg(long)]
+        sort: Option<String>,
     },
-    
+
     /// Get a specific secret by name or ID
     Get {
-        /// Name or ID of the secret
-        name_or_id: String,
-        
+        /// Name or ID of the secret. Omit when using --all.
+        name_or_id: Option<String>,
+
+        /// Iterate every secret and render each with its `display_*`
+        /// function instead of fetching one by name - for a full
+        /// audit/export-to-screen. Unlike 'kookie list', this renders full
+        /// secret bodies (masked unless --reveal), not just metadata.
+        /// Combine with the type filter flags below to narrow it, e.g.
+        /// `kookie get --all --passwords --reveal`.
+        #[arg(long)]
+        all: bool,
+
+        /// With --all, print every matching secret's value in full instead
+        /// of masked, after an interactive confirmation. Refuses to run
+        /// non-interactively.
+        #[arg(long)]
+        reveal: bool,
+
+        /// With --all, show only passwords
+        #[arg(long)]
+        passwords: bool,
+
+        /// With --all, show only API keys
+        #[arg(long = "api-keys")]
+        api_keys: bool,
+
+        /// With --all, show only notes
+        #[arg(long)]
+        notes: bool,
+
+        /// With --all, show only database credentials
+        #[arg(long)]
+        db: bool,
+
+        /// With --all, show only tokens
+        #[arg(long)]
+        tokens: bool,
+
+        /// With --all, show only custom secrets
+        #[arg(long)]
+        custom: bool,
+
+        /// With --all, show only SSH keys
+        #[arg(long)]
+        ssh: bool,
+
         /// Copy the secret value to clipboard
         #[arg(short, long)]
         copy: bool,
+
+        /// Print the secret, wait N seconds, then erase it from the terminal
+        #[arg(long)]
+        reveal_seconds: Option<u64>,
+
+        /// Print only the named field's raw value to stdout, undecorated
+        /// (e.g. `kookie get github --field password`), for capturing into
+        /// a shell variable. Errors if the field doesn't exist for the
+        /// matched type. For the clipboard instead of stdout, see --copy.
+        #[arg(long)]
+        field: Option<String>,
+
+        /// Extract an attached file by name instead of printing the secret.
+        /// For SSH keys, pass a directory instead of a filename to write the
+        /// private/public key pair back out with 0600/0644 permissions.
+        #[arg(long)]
+        extract: Option<String>,
+
+        /// Output path for --extract (default: the attachment's filename, in the current directory)
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// How much of the secret to reveal: "hidden", "partial" (last 4 chars), or "full"
+        /// (default: the configured `reveal_mode`, see `kookie config --show`)
+        #[arg(long)]
+        mask: Option<String>,
+
+        /// How `--copy` combines a password's fields: "value" (default), "login"
+        /// (username<TAB>password, for tabbing between form fields), or "lines"
+        /// (username<newline>password). No effect on other secret types.
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Print `export VARNAME='value'` (shell-escaped) instead of the
+        /// normal output, for `eval "$(kookie get db-url --export DATABASE_URL)"`
+        #[arg(long)]
+        export: Option<String>,
+
+        /// Re-encode the secret's value as "hex" or "base64" instead of its
+        /// stored form ("utf8", the default) before printing or copying it -
+        /// for a generated key that needs to be consumed in a different
+        /// format than how it was stored. No effect on the masked card view;
+        /// combine with --field, --export, --copy, or --reveal-seconds.
+        #[arg(long)]
+        encoding: Option<String>,
     },
-    
-    /// Delete a secret
+
+    /// Copy a secret's default value to the clipboard without ever
+    /// printing it to the terminal. A focused alternative to 'kookie get
+    /// --copy', for screen-sharing contexts.
+    Copy {
+        /// Name or ID of the secret
+        name_or_id: String,
+    },
+
+    /// Attach a file to a secret (e.g. a certificate or keyfile)
+    Attach {
+        /// Name or ID of the secret
+        name_or_id: String,
+
+        /// Path to the file to attach (capped at 1MB)
+        file: String,
+    },
+
+    /// Delete a secret. By default this moves it to the trash; use
+    /// `--permanent` to skip the trash entirely.
     Delete {
         /// Name or ID of the secret
         name_or_id: String,
-        
+
         /// Skip confirmation prompt
         #[arg(short, long)]
         force: bool,
+
+        /// Bypass the trash and delete outright. Requires --force.
+        #[arg(long)]
+        permanent: bool,
     },
-    
+
+    /// Restore a secret from the trash
+    Restore {
+        /// Name or ID of the trashed secret
+        name_or_id: String,
+    },
+
+    /// Permanently remove everything in the trash
+    EmptyTrash {
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+
     /// Generate random secrets
     Generate {
         #[command(subcommand)]
@@ -153,10 +425,17 @@ enum Commands {
         /// Set unlock timeout in minutes (0 to disable)
         #[arg(short, long)]
         timeout: Option<u32>,
-        
+
         /// Show current configuration
         #[arg(short, long)]
         show: bool,
+
+        /// Restore every setting to its default, after confirmation
+        #[arg(long)]
+        reset: bool,
+
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
     },
     
     /// Install kookie to system PATH
@@ -168,6 +447,258 @@ enum Commands {
     
     /// Uninstall kookie from system
     Uninstall,
+
+    /// Show vault and environment status
+    Status,
+
+    /// Run environment diagnostics (vault, PATH, clipboard, config, session, KDF)
+    Doctor,
+
+    /// Benchmark Argon2id KDF parameters (read-only, doesn't touch the vault)
+    BenchKdf {
+        /// Memory cost in KB
+        #[arg(long)]
+        memory: Option<u32>,
+
+        /// Time cost (number of iterations)
+        #[arg(long)]
+        iterations: Option<u32>,
+
+        /// Degree of parallelism
+        #[arg(long)]
+        parallelism: Option<u32>,
+    },
+
+    /// Re-save the vault as compact JSON and report the vault.json size
+    Compact {
+        /// Also report per-type secret counts and approximate plaintext bytes
+        #[arg(long)]
+        stats: bool,
+    },
+
+    /// Merge secrets from another vault's decrypted JSON export into this one
+    Import {
+        /// Path to the JSON file to import (a JSON-serialized `VaultData`),
+        /// or an age-encrypted file if --age-identity is given
+        file: std::path::PathBuf,
+
+        /// How to resolve a name collision: skip, overwrite, rename, or
+        /// prompt (ask for each one interactively)
+        #[arg(long, default_value = "prompt")]
+        on_conflict: String,
+
+        /// Path to an age identity (private key) file; if given, `file` is
+        /// decrypted as an age-encrypted backup (see `kookie export --age`)
+        /// before being merged. Requires the `age` feature.
+        #[arg(long)]
+        age_identity: Option<std::path::PathBuf>,
+    },
+
+    /// Export the vault's decrypted data to an age-encrypted file, for
+    /// offsite backups independent of kookie's own KDF/cipher. Requires the
+    /// `age` feature. Restore with `kookie import --age-identity <keyfile>`.
+    Export {
+        /// The age recipient (public key) to encrypt to
+        #[arg(long)]
+        age: String,
+
+        /// Path to write the encrypted backup to
+        file: std::path::PathBuf,
+    },
+
+    /// Push/pull the encrypted vault file against a configured remote, for
+    /// syncing between machines without decrypting client-side. Requires
+    /// the `sync` feature
+    Sync {
+        #[command(subcommand)]
+        action: SyncAction,
+    },
+
+    /// Version the encrypted vault file with git, for backups and history
+    /// independent of `sync`. Requires the `git` feature
+    Git {
+        #[command(subcommand)]
+        action: GitAction,
+    },
+
+    /// Run a command with secrets injected into its environment, never
+    /// writing them to disk or the parent shell (inspired by `envchain`)
+    Exec {
+        /// Inject every API key/token tagged with this into the child's
+        /// environment, as NAME=value with the secret's name uppercased
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Explicit one-off mapping: NAME=secret-name. Repeatable.
+        #[arg(long = "env", value_name = "NAME=secret-name")]
+        env: Vec<String>,
+
+        /// The command to run, and its arguments (put after `--`)
+        #[arg(trailing_var_arg = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Write a secret's value to a named pipe exactly once, then exit - for
+    /// init scripts and other tools that read a credential from a FIFO.
+    /// The pipe must already exist (`mkfifo <path>` first); see the
+    /// `serve_once` module docs for the security considerations around
+    /// using one.
+    ServeOnce {
+        /// Name or ID of the secret
+        name_or_id: String,
+
+        /// Path to the existing named pipe to write the value to
+        #[arg(long)]
+        out: std::path::PathBuf,
+
+        /// Give up waiting for a reader to open the pipe after this many
+        /// seconds instead of blocking indefinitely
+        #[arg(long)]
+        timeout_secs: Option<u64>,
+    },
+
+    /// Show the most recently added secrets across all types
+    Recent {
+        /// Maximum number of secrets to show
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Show secrets created or modified within a time window
+    Changelog {
+        /// How far back to look, e.g. "30m", "12h", "7d", "2w"
+        #[arg(long, default_value = "7d")]
+        since: String,
+    },
+
+    /// Split or reconstruct the vault key via Shamir secret sharing
+    Recovery {
+        #[command(subcommand)]
+        action: RecoveryAction,
+    },
+
+    /// Mark a secret as a favorite, so it sorts first in 'kookie list'
+    Fav {
+        /// Name or ID of the secret
+        name_or_id: String,
+    },
+
+    /// Remove a secret's favorite mark
+    Unfav {
+        /// Name or ID of the secret
+        name_or_id: String,
+    },
+
+    /// Mark a secret as burn-after-read: the next 'kookie get' shows it
+    /// once and then permanently deletes it
+    Burn {
+        /// Name or ID of the secret
+        name_or_id: String,
+    },
+
+    /// Clear a secret's burn-after-read mark
+    Unburn {
+        /// Name or ID of the secret
+        name_or_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RecoveryAction {
+    /// Split the unlocked vault's key into K-of-N shares, printed to stdout
+    Split {
+        /// Total number of shares to generate
+        #[arg(long)]
+        shares: u8,
+        /// Minimum number of shares required to reconstruct the key
+        #[arg(long)]
+        threshold: u8,
+    },
+    /// Reconstruct the vault key from pasted shares and cache a session
+    Combine,
+}
+
+#[derive(Subcommand)]
+enum SyncAction {
+    /// Configure the remote to sync against
+    SetRemote {
+        /// URL the encrypted vault file is uploaded to/downloaded from via
+        /// HTTP PUT/GET (a WebDAV path, or an S3-compatible endpoint that
+        /// accepts Basic auth, e.g. behind a presigned URL or gateway)
+        #[arg(long)]
+        url: String,
+
+        /// HTTP Basic auth username, if the remote requires one
+        #[arg(long)]
+        username: Option<String>,
+
+        /// HTTP Basic auth password, if the remote requires one
+        #[arg(long)]
+        password: Option<String>,
+
+        /// Allow a plain http:// remote, sending Basic auth credentials
+        /// unencrypted. Only meant for local testing (e.g. against a
+        /// WebDAV server on localhost); refused otherwise.
+        #[arg(long)]
+        allow_insecure_http: bool,
+    },
+    /// Upload the local vault file to the remote, overwriting it
+    Push,
+    /// Download the remote vault file and replace the local one, unless
+    /// both changed since the last sync
+    Pull,
+}
+
+#[derive(Subcommand)]
+enum GitAction {
+    /// Turn the vault directory into a git repository, with a `.gitignore`
+    /// excluding session/lock state and `config.json`
+    Init,
+    /// Show the vault directory's commit history, newest first
+    Log,
+    /// Check out `vault.json` as it was at an earlier commit, overwriting
+    /// the current one
+    Restore {
+        /// A commit hash, prefix, or other git revision
+        commit: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Set a config key to a value
+    Set {
+        /// Config key (e.g. timeout_minutes)
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Get a config key's current value
+    Get {
+        /// Config key (e.g. timeout_minutes)
+        key: String,
+    },
+    /// Define a custom 'generate password --policy' preset
+    AddPolicy {
+        /// Policy name (overrides a built-in of the same name)
+        name: String,
+
+        /// Minimum password length
+        #[arg(long)]
+        min_len: usize,
+
+        /// Require at least one symbol
+        #[arg(long)]
+        require_symbol: bool,
+
+        /// Require at least one digit
+        #[arg(long)]
+        require_digit: bool,
+
+        /// Characters to exclude from the generated password
+        #[arg(long, default_value = "")]
+        exclude: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -177,54 +708,246 @@ enum GenerateType {
         /// Copy to clipboard
         #[arg(short, long)]
         copy: bool,
+
+        /// Generate N values, printed one per line to stdout (incompatible with --copy)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Store the generated value in the vault as a token, instead of (or
+        /// in addition to) printing/copying it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Write the value to a file instead of printing it (no trailing
+        /// newline; 0o600 on Unix). Combined with --count, writes one
+        /// value per line.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// With --out, overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
     },
-    
+
     /// Generate a random key
     Key {
         /// Length in bytes (default: 32)
         #[arg(short, long)]
         length: Option<usize>,
-        
+
+        /// Base encoding for the output: hex, base64, base64url, raw (default: base64url)
+        #[arg(short, long)]
+        encoding: Option<String>,
+
         /// Copy to clipboard
         #[arg(short, long)]
         copy: bool,
+
+        /// Generate N values, printed one per line to stdout (incompatible with --copy)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Store the generated value in the vault as a token, instead of (or
+        /// in addition to) printing/copying it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Write the value to a file instead of printing it (no trailing
+        /// newline; 0o600 on Unix). Combined with --count, writes one
+        /// value per line.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// With --out, overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
     },
-    
+
     /// Generate a random password
     Password {
-        /// Length in characters (default: 16)
+        /// Length in characters (default: 16, or the policy's min_len if higher)
         #[arg(short, long)]
         length: Option<usize>,
-        
+
         /// Include symbols
         #[arg(short, long)]
         symbols: bool,
-        
+
+        /// Named policy to satisfy: strong, pin, alnum, or one defined via
+        /// 'kookie config add-policy' (overrides --symbols)
+        #[arg(long)]
+        policy: Option<String>,
+
         /// Copy to clipboard
         #[arg(short, long)]
         copy: bool,
+
+        /// Generate N values, printed one per line to stdout (incompatible with --copy)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Store the generated value in the vault as a password, instead of
+        /// (or in addition to) printing/copying it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
+
+        /// With --save, skip the optional username/URL prompts
+        #[arg(long)]
+        no_prompt: bool,
+
+        /// Write the value to a file instead of printing it (no trailing
+        /// newline; 0o600 on Unix). Combined with --count, writes one
+        /// value per line.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// With --out, overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
     },
-    
+
     /// Generate an API key with kk_ prefix
     #[command(name = "api-key")]
     ApiKey {
+        /// Prefix prepended to the random portion (default: kk_)
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Length in bytes of the random portion before encoding (default: 24)
+        #[arg(long)]
+        length: Option<usize>,
+
+        /// Copy to clipboard
+        #[arg(short, long)]
+        copy: bool,
+
+        /// Generate N values, printed one per line to stdout (incompatible with --copy)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Store the generated value in the vault as an API key, instead of
+        /// (or in addition to) printing/copying it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Write the value to a file instead of printing it (no trailing
+        /// newline; 0o600 on Unix). Combined with --count, writes one
+        /// value per line.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// With --out, overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Generate a UUID for use as a correlation ID
+    Uuid {
+        /// UUID version: "v4" (random, default) or "v7" (timestamp-prefixed, sortable)
+        #[arg(long = "uuid-version")]
+        uuid_version: Option<String>,
+
         /// Copy to clipboard
         #[arg(short, long)]
         copy: bool,
+
+        /// Generate N values, printed one per line to stdout (incompatible with --copy)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Store the generated value in the vault as a token, instead of
+        /// (or in addition to) printing/copying it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Write the value to a file instead of printing it (no trailing
+        /// newline; 0o600 on Unix). Combined with --count, writes one
+        /// value per line.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// With --out, overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Generate an SSH key pair
+    Ssh {
+        /// Key type to generate (only "ed25519" is currently supported)
+        #[arg(long, default_value = "ed25519")]
+        key_type: String,
+
+        /// Comment embedded in the public key (default: "kookie")
+        #[arg(long)]
+        comment: Option<String>,
+
+        /// Store the generated key pair in the vault as an SSH key, instead
+        /// of (or in addition to) printing it. Requires --name.
+        #[arg(long)]
+        save: bool,
+
+        /// Name to store the secret under when --save is set
+        #[arg(long)]
+        name: Option<String>,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
-    
-    let result = match cli.command {
-        Commands::Init { force } => commands::init::run(force),
+
+    if cli.no_color || std::env::var_os("NO_COLOR").is_some() {
+        colored::control::set_override(false);
+    }
+
+    let log_level = if cli.quiet {
+        utils::display::LogLevel::Quiet
+    } else if cli.verbose {
+        utils::display::LogLevel::Verbose
+    } else {
+        utils::display::LogLevel::Normal
+    };
+    utils::display::set_log_level(log_level);
+
+    if let Some(home) = cli.home {
+        vault::storage::set_home_override(home);
+    }
+
+    utils::input::set_auto_confirm(cli.yes);
+
+    let result: Result<(), CliError> = match cli.command {
+        Commands::Init { force, from_stdin_password, kdf_profile, import, age_identity } => {
+            commands::init::run(force, from_stdin_password, kdf_profile, cli.yes, import, age_identity)
+        }
         
         Commands::Lock => commands::lock::lock(),
         
-        Commands::Unlock { timeout } => commands::lock::unlock(timeout),
+        Commands::SetPin => commands::pin::set(),
+        Commands::Unlock { timeout, pin: true, .. } => commands::pin::unlock_with_pin(timeout),
+        Commands::Unlock { timeout, extend, print_remaining, pin: _ } => {
+            commands::lock::unlock(timeout, extend, print_remaining)
+        }
         
-        Commands::Add { password, api_key, note, db, token } => {
+        Commands::Add { password, api_key, note, db, token, custom, schema, ssh, value, confirm, name } => {
             let add_type = if password {
                 commands::add::AddType::Password
             } else if api_key {
@@ -235,6 +958,10 @@ fn main() {
                 commands::add::AddType::DbCredential
             } else if token {
                 commands::add::AddType::Token
+            } else if custom {
+                commands::add::AddType::Custom
+            } else if ssh {
+                commands::add::AddType::SshKey
             } else {
                 println!("{}", "Please specify a secret type:".yellow());
                 println!("  kookie add --password");
@@ -242,12 +969,34 @@ fn main() {
                 println!("  kookie add --note");
                 println!("  kookie add --db");
                 println!("  kookie add --token");
+                println!("  kookie add --custom");
+                println!("  kookie add --ssh");
                 return;
             };
-            commands::add::run(add_type)
+            commands::add::run(add_type, value, confirm, schema, name)
         }
-        
-        Commands::List { passwords, api_keys, notes, db, tokens } => {
+
+        Commands::List { passwords, api_keys, notes, db, tokens, custom, ssh, favorites, reveal, format, count_only, json, env, names_only, null, sort } => {
+            let format = match format {
+                Some(f) => match f.parse() {
+                    Ok(fmt) => fmt,
+                    Err(msg) => {
+                        eprintln!("{} {}", "Error:".red().bold(), msg);
+                        std::process::exit(1);
+                    }
+                },
+                None => commands::list::ListFormat::default(),
+            };
+            let sort = match sort {
+                Some(s) => match s.parse() {
+                    Ok(mode) => Some(mode),
+                    Err(msg) => {
+                        eprintln!("{} {}", "Error:".red().bold(), msg);
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
             let filter = if passwords {
                 commands::list::ListFilter::Passwords
             } else if api_keys {
@@ -258,42 +1007,238 @@ fn main() {
                 commands::list::ListFilter::DbCredentials
             } else if tokens {
                 commands::list::ListFilter::Tokens
+            } else if custom {
+                commands::list::ListFilter::Custom
+            } else if ssh {
+                commands::list::ListFilter::SshKey
             } else {
                 commands::list::ListFilter::All
             };
-            commands::list::run(filter)
+            commands::list::run(filter, reveal, favorites, format, count_only, json, env, names_only, null, sort)
         }
         
-        Commands::Get { name_or_id, copy } => commands::get::run(&name_or_id, copy),
-        
-        Commands::Delete { name_or_id, force } => commands::delete::run(&name_or_id, force),
-        
+        Commands::Get {
+            name_or_id, all, reveal, passwords, api_keys, notes, db, tokens, custom, ssh,
+            copy, reveal_seconds, field, extract, out, mask, format, export, encoding,
+        } => {
+            let mask = match mask {
+                Some(m) => match m.parse() {
+                    Ok(mode) => Some(mode),
+                    Err(msg) => {
+                        eprintln!("{} {}", "Error:".red().bold(), msg);
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
+
+            if all {
+                let filter = if passwords {
+                    commands::list::ListFilter::Passwords
+                } else if api_keys {
+                    commands::list::ListFilter::ApiKeys
+                } else if notes {
+                    commands::list::ListFilter::Notes
+                } else if db {
+                    commands::list::ListFilter::DbCredentials
+                } else if tokens {
+                    commands::list::ListFilter::Tokens
+                } else if custom {
+                    commands::list::ListFilter::Custom
+                } else if ssh {
+                    commands::list::ListFilter::SshKey
+                } else {
+                    commands::list::ListFilter::All
+                };
+                commands::get::run_all(filter, reveal, mask)
+            } else {
+                let format = match format {
+                    Some(f) => match f.parse() {
+                        Ok(fmt) => fmt,
+                        Err(msg) => {
+                            eprintln!("{} {}", "Error:".red().bold(), msg);
+                            std::process::exit(1);
+                        }
+                    },
+                    None => commands::get::CopyFormat::default(),
+                };
+                let name_or_id = match name_or_id {
+                    Some(n) => n,
+                    None => {
+                        eprintln!("{} NAME_OR_ID is required unless --all is given", "Error:".red().bold());
+                        std::process::exit(1);
+                    }
+                };
+                let encoding = match encoding {
+                    Some(e) => match e.parse() {
+                        Ok(enc) => enc,
+                        Err(msg) => {
+                            eprintln!("{} {}", "Error:".red().bold(), msg);
+                            std::process::exit(1);
+                        }
+                    },
+                    None => utils::display::Encoding::default(),
+                };
+                commands::get::run(&name_or_id, copy, reveal_seconds, field, extract, out, mask, format, export, encoding)
+            }
+        }
+
+        Commands::Copy { name_or_id } => commands::copy::run(&name_or_id),
+
+        Commands::Attach { name_or_id, file } => commands::attach::run(&name_or_id, &file),
+
+        Commands::Delete { name_or_id, force, permanent } => commands::delete::run(&name_or_id, force, permanent),
+
+        Commands::Restore { name_or_id } => commands::trash::restore(&name_or_id),
+
+        Commands::EmptyTrash { force } => commands::trash::empty(force),
+
         Commands::Generate { gen_type } => {
             match gen_type {
-                GenerateType::Jwt { copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Jwt, None, copy, false)
+                GenerateType::Jwt { copy, count, save, name, out, force } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::Jwt, None, copy, false, None, count, None, None, save, name, false, out, force,
+                    )
+                }
+                GenerateType::Key { length, encoding, copy, count, save, name, out, force } => {
+                    let encoding = match encoding {
+                        Some(e) => match e.parse() {
+                            Ok(enc) => Some(enc),
+                            Err(msg) => {
+                                eprintln!("{} {}", "Error:".red().bold(), msg);
+                                std::process::exit(1);
+                            }
+                        },
+                        None => None,
+                    };
+                    commands::generate::run(
+                        commands::generate::GenerateType::Key, length, copy, false, encoding, count, None, None, save, name, false, out, force,
+                    )
                 }
-                GenerateType::Key { length, copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Key, length, copy, false)
+                GenerateType::Password { length, symbols, policy, copy, count, save, name, no_prompt, out, force } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::Password, length, copy, symbols, None, count, None, policy, save, name, no_prompt, out, force,
+                    )
                 }
-                GenerateType::Password { length, symbols, copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Password, length, copy, symbols)
+                GenerateType::ApiKey { prefix, length, copy, count, save, name, out, force } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::ApiKey, length, copy, false, None, count, prefix, None, save, name, false, out, force,
+                    )
                 }
-                GenerateType::ApiKey { copy } => {
-                    commands::generate::run(commands::generate::GenerateType::ApiKey, None, copy, false)
+                GenerateType::Uuid { uuid_version, copy, count, save, name, out, force } => {
+                    let version = match uuid_version {
+                        Some(v) => match v.parse() {
+                            Ok(ver) => ver,
+                            Err(msg) => {
+                                eprintln!("{} {}", "Error:".red().bold(), msg);
+                                std::process::exit(1);
+                            }
+                        },
+                        None => utils::generators::UuidVersion::default(),
+                    };
+                    commands::generate::run(
+                        commands::generate::GenerateType::Uuid(version), None, copy, false, None, count, None, None, save, name, false, out, force,
+                    )
+                }
+                GenerateType::Ssh { key_type, comment, save, name } => {
+                    commands::generate::run_ssh(key_type, comment, save, name)
                 }
             }
         }
         
-        Commands::Config { timeout, show } => commands::config::run(timeout, show),
+        Commands::Config { timeout, show, reset, action } => {
+            let action = action.map(|a| match a {
+                ConfigAction::Set { key, value } => commands::config::ConfigAction::Set { key, value },
+                ConfigAction::Get { key } => commands::config::ConfigAction::Get { key },
+                ConfigAction::AddPolicy { name, min_len, require_symbol, require_digit, exclude } => {
+                    commands::config::ConfigAction::AddPolicy {
+                        name,
+                        min_len,
+                        require_symbol,
+                        require_digit,
+                        exclude,
+                    }
+                }
+            });
+            commands::config::run(timeout, show, reset, action)
+        }
         
         Commands::Install { force } => commands::install::run(force),
         
         Commands::Uninstall => commands::install::uninstall(),
+
+        Commands::Status => commands::status::run(),
+
+        Commands::Doctor => commands::doctor::run(),
+
+        Commands::BenchKdf { memory, iterations, parallelism } => {
+            commands::bench_kdf::run(memory, iterations, parallelism)
+        }
+
+        Commands::Compact { stats } => commands::compact::run(stats),
+
+        Commands::Import { file, on_conflict, age_identity } => {
+            let on_conflict = match on_conflict.parse() {
+                Ok(strategy) => strategy,
+                Err(msg) => {
+                    eprintln!("{} {}", "Error:".red().bold(), msg);
+                    std::process::exit(1);
+                }
+            };
+            commands::import::run(&file, on_conflict, age_identity.as_deref())
+        }
+
+        Commands::Export { age, file } => commands::export::run(&age, &file),
+
+        Commands::Sync { action } => match action {
+            SyncAction::SetRemote { url, username, password, allow_insecure_http } => {
+                commands::sync::set_remote(url, username, password, allow_insecure_http)
+            }
+            SyncAction::Push => commands::sync::push(),
+            SyncAction::Pull => commands::sync::pull(),
+        },
+
+        Commands::Git { action } => match action {
+            GitAction::Init => commands::git::init(),
+            GitAction::Log => commands::git::log(),
+            GitAction::Restore { commit } => commands::git::restore(&commit),
+        },
+
+        Commands::Exec { tag, env, command } => commands::exec::run(tag, env, command),
+
+        Commands::ServeOnce { name_or_id, out, timeout_secs } => {
+            commands::serve_once::run(&name_or_id, &out, timeout_secs)
+        }
+
+        Commands::Recent { limit } => commands::recent::run(limit),
+
+        Commands::Changelog { since } => {
+            let parsed = match utils::duration::parse_duration(&since) {
+                Ok(d) => d,
+                Err(msg) => {
+                    eprintln!("{} {}", "Error:".red().bold(), msg);
+                    std::process::exit(1);
+                }
+            };
+            commands::changelog::run(&since, parsed)
+        }
+
+        Commands::Recovery { action } => match action {
+            RecoveryAction::Split { shares, threshold } => commands::recovery::split(shares, threshold),
+            RecoveryAction::Combine => commands::recovery::combine(),
+        },
+
+        Commands::Fav { name_or_id } => commands::favorite::add(&name_or_id),
+
+        Commands::Unfav { name_or_id } => commands::favorite::remove(&name_or_id),
+
+        Commands::Burn { name_or_id } => commands::burn::add(&name_or_id),
+
+        Commands::Unburn { name_or_id } => commands::burn::remove(&name_or_id),
     };
     
     if let Err(e) = result {
         eprintln!("{} {}", "Error:".red().bold(), e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
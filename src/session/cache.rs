@@ -2,35 +2,294 @@
 //!
 //! This module manages the temporary session that keeps the vault unlocked
 //! for a configurable duration without re-entering the master password.
+//!
+//! `get_cached_key` also re-locks early, before `expires_at`, if the
+//! machine appears to have suspended and resumed since the session was
+//! cached (see `suspended_since`) - so a laptop left unlocked doesn't stay
+//! that way across a lid close. This is Linux-only, via `/proc/uptime`;
+//! elsewhere (and wherever `/proc/uptime` can't be read) the check is
+//! skipped and sessions are trusted for their full configured timeout.
 
+use crate::utils::generators::PasswordPolicy;
 use crate::vault::storage;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
 /// Session configuration
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SessionConfig {
     /// Timeout in minutes (0 = always ask for password)
     pub timeout_minutes: u32,
+    /// Whether to mark clipboard copies as excluded from Windows 10+
+    /// Clipboard History and Cloud Clipboard sync. No-op outside Windows.
+    #[serde(default = "default_clipboard_history_protection")]
+    pub clipboard_history_protection: bool,
+    /// User-defined `generate password --policy` presets, keyed by name.
+    /// The built-in presets (`strong`, `pin`, `alnum`) aren't stored here;
+    /// a custom policy with the same name takes priority over a built-in.
+    #[serde(default)]
+    pub policies: HashMap<String, PasswordPolicy>,
+    /// Number of master password attempts allowed before `unlock`/
+    /// `ensure_unlocked` gives up, with an increasing delay between them.
+    #[serde(default = "default_unlock_attempts")]
+    pub unlock_attempts: u32,
+    /// Default reveal mode for `display_*` functions (e.g. `get`'s output),
+    /// overridable per-invocation with `get --mask`.
+    #[serde(default)]
+    pub reveal_mode: crate::utils::display::RevealMode,
+    /// Maximum combined size, in bytes, of all attachments on a single
+    /// secret. Independent of `Attachment::MAX_SIZE_BYTES`, which caps one
+    /// file at a time; this caps how many of those a secret can accumulate.
+    #[serde(default = "default_max_total_attachment_bytes")]
+    pub max_total_attachment_bytes: u64,
+    /// Days a deleted secret stays in the trash before `ensure_unlocked`
+    /// purges it for good.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Team-configured default values shown (in brackets) for `add_*`
+    /// prompts and used on empty input, keyed by "<type>.<field>" (e.g.
+    /// "password.url", "note.description"). Lets a team with conventions
+    /// pre-fill the common case instead of re-typing it on every `add`.
+    #[serde(default)]
+    pub prompts: HashMap<String, String>,
+    /// Which on-disk layout `Vault::save`/`Vault::unlock` use for the seven
+    /// secret-type vectors. See `storage::StorageBackend`.
+    #[serde(default)]
+    pub storage_backend: storage::StorageBackend,
+    /// External command `clipboard::copy_to_clipboard` pipes the secret
+    /// into via stdin instead of using the native `arboard` backend (e.g.
+    /// `"wl-copy"`, `"xclip -selection clipboard"`), for setups where
+    /// `arboard` can't find a clipboard to talk to. Unset by default.
+    #[serde(default)]
+    pub clipboard_command: Option<String>,
+    /// Whether `add_*` should normalize secret names (lowercase, collapse
+    /// spaces/underscores to hyphens) before storing them, so e.g. "GitHub"
+    /// and "git hub" collide as the same duplicate instead of fragmenting
+    /// the vault into near-identical entries. Off by default, since it
+    /// changes names users already typed.
+    #[serde(default)]
+    pub normalize_names: bool,
+    /// Character `print_secret`/`print_secret_partial` repeat to mask a
+    /// hidden secret value (default `•`).
+    #[serde(default = "default_mask_char")]
+    pub mask_char: char,
+    /// URL `kookie sync push`/`sync pull` upload/download the encrypted
+    /// vault file against, via HTTP PUT/GET. Set with
+    /// `kookie sync set-remote`. Unset by default.
+    #[serde(default)]
+    pub sync_remote_url: Option<String>,
+    /// HTTP Basic auth username sent with every sync request, if set.
+    #[serde(default)]
+    pub sync_remote_username: Option<String>,
+    /// HTTP Basic auth password sent with every sync request, if set.
+    /// Stored in plaintext in `config.json`, same as `clipboard_command`.
+    #[serde(default)]
+    pub sync_remote_password: Option<String>,
+    /// Base64 SHA-256 hash of the local `vault.json` as of the last
+    /// successful `sync push`/`sync pull`, used by `sync pull` to tell
+    /// whether the local vault changed since then.
+    #[serde(default)]
+    pub sync_last_local_hash: Option<String>,
+    /// ETag of the remote vault file as of the last successful
+    /// `sync push`/`sync pull`, used by `sync pull` to tell whether the
+    /// remote changed since then.
+    #[serde(default)]
+    pub sync_last_remote_etag: Option<String>,
+    /// Whether `Vault::save`/`Vault::mutate` should auto-commit the vault
+    /// directory (via `kookie git`) after every change. Only takes effect
+    /// once `kookie git init` has turned the vault directory into a git
+    /// repository; off by default.
+    #[serde(default)]
+    pub git_autocommit: bool,
+}
+
+fn default_clipboard_history_protection() -> bool {
+    true
+}
+
+fn default_unlock_attempts() -> u32 {
+    3
+}
+
+fn default_max_total_attachment_bytes() -> u64 {
+    10 * 1024 * 1024 // 10 MB
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_mask_char() -> char {
+    '•'
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             timeout_minutes: 10, // Default 10 minutes
+            clipboard_history_protection: default_clipboard_history_protection(),
+            policies: HashMap::new(),
+            unlock_attempts: default_unlock_attempts(),
+            reveal_mode: crate::utils::display::RevealMode::default(),
+            max_total_attachment_bytes: default_max_total_attachment_bytes(),
+            trash_retention_days: default_trash_retention_days(),
+            prompts: HashMap::new(),
+            storage_backend: storage::StorageBackend::default(),
+            clipboard_command: None,
+            normalize_names: false,
+            mask_char: default_mask_char(),
+            sync_remote_url: None,
+            sync_remote_username: None,
+            sync_remote_password: None,
+            sync_last_local_hash: None,
+            sync_last_remote_etag: None,
+            git_autocommit: false,
         }
     }
 }
 
-/// Session data stored on disk
+/// Session data stored on disk (or, with the `keyring` feature, in the OS
+/// keychain instead).
 #[derive(Serialize, Deserialize)]
 struct SessionData {
-    /// Encrypted key (encrypted with a machine-specific key)
+    /// The session key, encoded for storage. When persisted to disk this is
+    /// XOR-obfuscated with a machine-specific key (see `get_machine_key`);
+    /// when persisted to the OS keychain it's the raw key, since the
+    /// keychain already encrypts its contents at rest.
     key_data: String,
     /// Expiration time
     expires_at: DateTime<Utc>,
+    /// Wall-clock time the session was saved. Paired with
+    /// `boot_marker_secs` so `get_cached_key` can tell a suspend/resume
+    /// cycle apart from ordinary elapsed time. `None` for sessions saved
+    /// before this field existed.
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    /// `/proc/uptime` (Linux only, see `monotonic_uptime_secs`) at save
+    /// time. Suspend doesn't advance it at the same rate as the wall
+    /// clock, so a gap between how much it's moved and how much
+    /// `created_at` says should have passed means the machine slept since
+    /// the session was cached - see `get_cached_key`.
+    #[serde(default)]
+    boot_marker_secs: Option<f64>,
+}
+
+/// Seconds of slack between wall-clock and monotonic elapsed time before
+/// `get_cached_key` treats the gap as a suspend/resume rather than normal
+/// scheduling jitter.
+const SUSPEND_SLACK_SECONDS: f64 = 5.0;
+
+/// Reads seconds since boot from `/proc/uptime` (Linux only). This counter
+/// is expected to lag wall-clock time across a suspend/resume cycle, which
+/// is what lets `get_cached_key` detect one; `None` elsewhere (or if the
+/// file can't be read/parsed), in which case the suspend check is simply
+/// skipped and the cached session is trusted for its full timeout as
+/// before.
+#[cfg(target_os = "linux")]
+fn monotonic_uptime_secs() -> Option<f64> {
+    let content = fs::read_to_string("/proc/uptime").ok()?;
+    content.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn monotonic_uptime_secs() -> Option<f64> {
+    None
+}
+
+/// True if `session` was cached before a suspend/resume cycle that has
+/// since happened, based on how far `/proc/uptime` has lagged behind the
+/// wall-clock time elapsed since `created_at`. Linux only - see
+/// `monotonic_uptime_secs`.
+fn suspended_since(session: &SessionData) -> bool {
+    let (Some(created_at), Some(stored_marker)) = (session.created_at, session.boot_marker_secs) else {
+        return false;
+    };
+    let Some(current_marker) = monotonic_uptime_secs() else {
+        return false;
+    };
+    let wall_elapsed = (Utc::now() - created_at).num_milliseconds() as f64 / 1000.0;
+    let monotonic_elapsed = current_marker - stored_marker;
+    monotonic_elapsed < wall_elapsed - SUSPEND_SLACK_SECONDS
+}
+
+/// OS keychain-backed session storage (Keychain on macOS, Secret Service on
+/// Linux, Credential Manager on Windows), used in place of the file-based
+/// `.session` obfuscation when built with the `keyring` feature. Callers
+/// fall back to the file-based path if any of these fail, e.g. no keychain
+/// daemon is running.
+#[cfg(feature = "keyring")]
+mod keychain {
+    use super::SessionData;
+    use keyring::Entry;
+
+    const SERVICE: &str = "kookie";
+    const USERNAME: &str = "session";
+
+    fn entry() -> Result<Entry, String> {
+        Entry::new(SERVICE, USERNAME).map_err(|e| e.to_string())
+    }
+
+    pub fn save(session: &SessionData) -> Result<(), String> {
+        let payload = serde_json::to_string(session).map_err(|e| e.to_string())?;
+        entry()?.set_password(&payload).map_err(|e| e.to_string())
+    }
+
+    pub fn load() -> Option<SessionData> {
+        let payload = entry().ok()?.get_password().ok()?;
+        serde_json::from_str(&payload).ok()
+    }
+
+    pub fn clear() {
+        if let Ok(e) = entry() {
+            let _ = e.delete_credential();
+        }
+    }
+}
+
+/// Writes `content` to `path` atomically: written to a unique temp file
+/// first, then renamed into place. Without this, a reader racing a
+/// concurrent writer's `fs::write` can observe a half-written file -
+/// `rename` is atomic, a plain `write` is not. On Unix the file is
+/// tightened to `0600` right after the rename, mirroring `save_vault_file` -
+/// this holds the obfuscated session key, so it shouldn't be group/other
+/// readable.
+///
+/// The temp file name includes both the process id and a per-process
+/// call counter, so two concurrent *processes* each get their own temp
+/// file (the common case - separate `kookie` invocations), and so do two
+/// concurrent *threads* within the same process (tests spawn writers this
+/// way; a process-id-only name would let them collide on the same temp
+/// file and have one clobber the other's write before the rename).
+///
+/// Unlike `vault::storage::VaultLock`, there's no `VaultLock`-style advisory
+/// lock around this: `vault::Vault::mutate` needs one because it reads the
+/// current secrets, merges in a change, and writes the merge back, so two
+/// concurrent writers can otherwise lose one side's edit. Every caller here
+/// (`save_session`, `save_pin_session`, `save_config`) instead builds a
+/// complete replacement value from scratch and writes it whole - two
+/// concurrent `unlock --extend`s just race on which one's expiry wins,
+/// exactly as if they'd run one after the other, and the atomic rename
+/// guarantees the loser's write is never torn or partially visible. Locking
+/// would serialize the writes but couldn't change that outcome, since
+/// there's nothing to merge.
+fn write_atomic(path: &Path, content: &str) -> Result<(), std::io::Error> {
+    static CALL_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let call_id = CALL_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp_path = path.with_extension(format!("tmp.{}.{}", std::process::id(), call_id));
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
 }
 
 /// Gets a simple machine-specific key for session encryption
@@ -70,6 +329,28 @@ pub fn save_session(key: &[u8; 32], timeout_minutes: u32) -> Result<(), std::io:
         return Ok(()); // Don't save session if timeout is 0
     }
 
+    #[cfg(feature = "keyring")]
+    {
+        let session = SessionData {
+            key_data: BASE64.encode(key),
+            expires_at: Utc::now() + Duration::minutes(timeout_minutes as i64),
+            created_at: Some(Utc::now()),
+            boot_marker_secs: monotonic_uptime_secs(),
+        };
+        match keychain::save(&session) {
+            Ok(()) => {
+                crate::utils::display::verbose("Session saved to OS keychain");
+                // Clear any stale file-based session so a later keychain
+                // failure doesn't resurrect an old key.
+                let _ = clear_session_file();
+                return Ok(());
+            }
+            Err(e) => crate::utils::display::verbose(&format!(
+                "OS keychain unavailable ({e}); falling back to file-based session"
+            )),
+        }
+    }
+
     let machine_key = get_machine_key();
     
     // XOR the key with machine key for basic obfuscation
@@ -81,17 +362,42 @@ pub fn save_session(key: &[u8; 32], timeout_minutes: u32) -> Result<(), std::io:
     let session = SessionData {
         key_data: BASE64.encode(obfuscated),
         expires_at: Utc::now() + Duration::minutes(timeout_minutes as i64),
+        created_at: Some(Utc::now()),
+        boot_marker_secs: monotonic_uptime_secs(),
     };
 
     let path = storage::get_session_path();
     let content = serde_json::to_string(&session)?;
-    fs::write(path, content)?;
+    write_atomic(&path, &content)?;
     
     Ok(())
 }
 
+/// Saves a session like `save_session`, but treats a read-only vault
+/// directory as a non-fatal condition: read commands (`get`, `list`, ...)
+/// refresh the session purely for convenience, and shouldn't fail outright
+/// just because the vault happens to be mounted read-only (e.g. a backup).
+/// Any other I/O error - a genuine disk problem - still propagates.
+pub fn save_session_best_effort(key: &[u8; 32], timeout_minutes: u32) -> Result<(), std::io::Error> {
+    match save_session(key, timeout_minutes) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::ReadOnlyFilesystem => {
+            crate::utils::display::warning(
+                "Vault directory is read-only; continuing without refreshing the session.",
+            );
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
 /// Gets the cached key if session is still valid
 pub fn get_cached_key() -> Option<[u8; 32]> {
+    #[cfg(feature = "keyring")]
+    if let Some(key) = get_cached_key_keychain() {
+        return Some(key);
+    }
+
     let path = storage::get_session_path();
     
     if !path.exists() {
@@ -103,10 +409,22 @@ pub fn get_cached_key() -> Option<[u8; 32]> {
     
     // Check if expired
     if session.expires_at < Utc::now() {
+        crate::utils::display::verbose("Cached session expired; clearing it");
         let _ = clear_session();
         return None;
     }
-    
+
+    if suspended_since(&session) {
+        crate::utils::display::verbose("System suspended since session was cached; re-locking");
+        let _ = clear_session();
+        return None;
+    }
+
+    crate::utils::display::verbose(&format!(
+        "Using cached session (expires {})",
+        session.expires_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+
     // Decode and de-obfuscate
     let obfuscated = BASE64.decode(&session.key_data).ok()?;
     if obfuscated.len() != 32 {
@@ -122,8 +440,151 @@ pub fn get_cached_key() -> Option<[u8; 32]> {
     Some(key)
 }
 
-/// Clears the session
-pub fn clear_session() -> Result<(), std::io::Error> {
+/// Reads and decodes the session key from the OS keychain, clearing it if
+/// expired. Returns `None` if no keychain entry exists, it's expired, or
+/// decoding fails for any reason (the caller falls back to the file-based
+/// session in that case).
+#[cfg(feature = "keyring")]
+fn get_cached_key_keychain() -> Option<[u8; 32]> {
+    let session = keychain::load()?;
+
+    if session.expires_at < Utc::now() {
+        crate::utils::display::verbose("Cached keychain session expired; clearing it");
+        keychain::clear();
+        return None;
+    }
+
+    if suspended_since(&session) {
+        crate::utils::display::verbose("System suspended since session was cached; re-locking");
+        keychain::clear();
+        return None;
+    }
+
+    crate::utils::display::verbose(&format!(
+        "Using cached keychain session (expires {})",
+        session.expires_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+
+    let decoded = BASE64.decode(&session.key_data).ok()?;
+    if decoded.len() != 32 {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&decoded);
+    Some(key)
+}
+
+/// A PIN-wrapped copy of a session key, as stored on disk. The PIN itself
+/// is never stored - only a salt and the session key encrypted with a key
+/// derived from it - so recovering the session key still requires the PIN.
+#[derive(Serialize, Deserialize)]
+struct PinSessionData {
+    /// Salt for deriving the PIN's wrapping key, distinct from the vault's
+    /// own KDF salt.
+    pin_salt: String,
+    /// The session key, encrypted with a key derived from the PIN.
+    wrapped_key: String,
+    /// Copied from the session's own `expires_at` when the PIN was set;
+    /// wrapping with a PIN never extends how long a session lasts.
+    expires_at: DateTime<Utc>,
+}
+
+/// The material `commands::pin` needs to recover a session key from a PIN:
+/// the salt to re-derive the wrapping key, and the key it should decrypt.
+pub struct PinSession {
+    pub pin_salt: String,
+    pub wrapped_key: String,
+}
+
+/// Stores a PIN-wrapped copy of a session key, so `unlock --pin` can
+/// reactivate it later without the master password. Overwrites any
+/// previous PIN.
+pub fn save_pin_session(pin_salt: String, wrapped_key: String, expires_at: DateTime<Utc>) -> Result<(), std::io::Error> {
+    storage::ensure_vault_dir().map_err(|e| std::io::Error::other(e.to_string()))?;
+    let data = PinSessionData { pin_salt, wrapped_key, expires_at };
+    let content = serde_json::to_string(&data)?;
+    write_atomic(&storage::get_pin_session_path(), &content)
+}
+
+/// Returns the stored PIN-wrapped session, if one exists and hasn't
+/// outlived the session it was wrapped from. Auto-clears it once expired,
+/// same as `get_cached_key` does for the regular session.
+pub fn load_pin_session() -> Option<PinSession> {
+    let path = storage::get_pin_session_path();
+    if !path.exists() {
+        return None;
+    }
+
+    let content = fs::read_to_string(&path).ok()?;
+    let data: PinSessionData = serde_json::from_str(&content).ok()?;
+
+    if data.expires_at < Utc::now() {
+        crate::utils::display::verbose("PIN-wrapped session expired; clearing it");
+        let _ = clear_pin_session();
+        return None;
+    }
+
+    Some(PinSession { pin_salt: data.pin_salt, wrapped_key: data.wrapped_key })
+}
+
+/// Clears the stored PIN, if any. Independent of the regular session - an
+/// explicit `kookie lock` does not call this, so a PIN keeps working for
+/// quick reactivation until its wrapped session's own expiry.
+pub fn clear_pin_session() -> Result<(), std::io::Error> {
+    let path = storage::get_pin_session_path();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Returns the current session's expiry, without decoding the key itself.
+/// Used by `commands::pin::set` to stamp a PIN-wrapped session with the
+/// same expiry as the session it wraps.
+pub(crate) fn session_expiry() -> Option<DateTime<Utc>> {
+    #[cfg(feature = "keyring")]
+    if let Some(session) = keychain::load() {
+        return if session.expires_at > Utc::now() { Some(session.expires_at) } else { None };
+    }
+
+    let path = storage::get_session_path();
+    let content = fs::read_to_string(path).ok()?;
+    let session: SessionData = serde_json::from_str(&content).ok()?;
+    if session.expires_at > Utc::now() {
+        Some(session.expires_at)
+    } else {
+        None
+    }
+}
+
+/// Returns the whole minutes remaining on the current cached session, if
+/// a valid (unexpired) session exists. Used by `unlock --print-remaining`.
+pub fn session_remaining_minutes() -> Option<i64> {
+    #[cfg(feature = "keyring")]
+    if let Some(session) = keychain::load() {
+        let remaining = session.expires_at - Utc::now();
+        return if remaining <= Duration::zero() { None } else { Some(remaining.num_minutes().max(1)) };
+    }
+
+    let path = storage::get_session_path();
+    if !path.exists() {
+        return None;
+    }
+
+    let content = fs::read_to_string(&path).ok()?;
+    let session: SessionData = serde_json::from_str(&content).ok()?;
+
+    let remaining = session.expires_at - Utc::now();
+    if remaining <= Duration::zero() {
+        return None;
+    }
+    Some(remaining.num_minutes().max(1))
+}
+
+/// Clears the session from the file, independent of the keychain. Used
+/// internally so that switching to a keychain-backed session doesn't leave
+/// a stale key on disk.
+fn clear_session_file() -> Result<(), std::io::Error> {
     let path = storage::get_session_path();
     if path.exists() {
         fs::remove_file(path)?;
@@ -131,6 +592,35 @@ pub fn clear_session() -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Clears the session, from the OS keychain and the file alike
+pub fn clear_session() -> Result<(), std::io::Error> {
+    #[cfg(feature = "keyring")]
+    keychain::clear();
+
+    clear_session_file()
+}
+
+/// Sets an explicit "locked" state, independent of session expiry.
+///
+/// `kookie lock` uses this so the vault stays locked until an explicit
+/// `unlock`, even if the configured timeout would otherwise still honor
+/// a cached key.
+pub fn set_locked(locked: bool) -> Result<(), std::io::Error> {
+    let path = storage::get_lock_flag_path();
+    if locked {
+        storage::ensure_vault_dir().map_err(|e| std::io::Error::other(e.to_string()))?;
+        fs::write(path, "")?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Returns whether the vault has been explicitly locked via `kookie lock`
+pub fn is_locked() -> bool {
+    storage::get_lock_flag_path().exists()
+}
+
 /// Loads session configuration
 pub fn load_config() -> SessionConfig {
     let path = storage::get_config_path();
@@ -146,9 +636,96 @@ pub fn load_config() -> SessionConfig {
 
 /// Saves session configuration
 pub fn save_config(config: &SessionConfig) -> Result<(), std::io::Error> {
-    storage::ensure_vault_dir().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+    storage::ensure_vault_dir().map_err(|e| std::io::Error::other(e.to_string()))?;
     let path = storage::get_config_path();
     let content = serde_json::to_string_pretty(config)?;
-    fs::write(path, content)?;
+    fs::write(&path, content)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_cached_key_returns_none_on_truncated_session_file() {
+        let _guard = storage::KOOKIE_HOME_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KOOKIE_HOME", dir.path());
+        storage::ensure_vault_dir().unwrap();
+
+        // Simulates a reader racing a writer mid-`fs::write`: valid JSON
+        // up to a point, then nothing.
+        fs::write(storage::get_session_path(), r#"{"key_data": "abc"#).unwrap();
+
+        assert!(get_cached_key().is_none());
+
+        std::env::remove_var("KOOKIE_HOME");
+    }
+
+    #[test]
+    fn test_concurrent_session_saves_never_corrupt_the_file() {
+        let _guard = storage::KOOKIE_HOME_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KOOKIE_HOME", dir.path());
+        storage::ensure_vault_dir().unwrap();
+
+        // `write_atomic` has no VaultLock-style mutual exclusion (see its
+        // doc comment for why a full-replacement write doesn't need one) -
+        // this proves that still holds under real concurrent writers: one
+        // writer's save always wins outright, never a torn mix of two.
+        let handles: Vec<_> = (0..5u8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let key = [i; 32];
+                    save_session(&key, 30).unwrap();
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let key = get_cached_key().expect("one of the writers should have won outright");
+        assert!((0..5u8).any(|i| key == [i; 32]), "session key should match exactly one writer, not a mix");
+
+        std::env::remove_var("KOOKIE_HOME");
+    }
+
+    #[test]
+    fn test_suspended_since_flags_a_monotonic_gap() {
+        // Only meaningful where `/proc/uptime` is readable (Linux); this
+        // is a no-op test elsewhere since `suspended_since` always returns
+        // `false` there.
+        if let Some(current_marker) = monotonic_uptime_secs() {
+            let session = SessionData {
+                key_data: String::new(),
+                expires_at: Utc::now() + Duration::minutes(10),
+                created_at: Some(Utc::now() - Duration::minutes(1)),
+                // A minute of wall-clock time passed but the monotonic
+                // marker barely moved - as if the machine slept for most
+                // of it.
+                boot_marker_secs: Some(current_marker - 0.1),
+            };
+            assert!(suspended_since(&session));
+        }
+    }
+
+    #[test]
+    fn test_suspended_since_ignores_sessions_without_a_marker() {
+        let session = SessionData {
+            key_data: String::new(),
+            expires_at: Utc::now() + Duration::minutes(10),
+            created_at: None,
+            boot_marker_secs: None,
+        };
+        assert!(!suspended_since(&session));
+    }
+}
@@ -1,10 +1,137 @@
 //! Clipboard utilities
 
 use arboard::Clipboard;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Clipboard errors
+#[derive(Error, Debug)]
+pub enum ClipboardError {
+    #[error("No clipboard backend available on this system")]
+    Unavailable,
+    #[error("Clipboard operation failed: {0}")]
+    Other(String),
+    #[error("Clipboard read-back didn't match what was copied")]
+    VerificationMismatch,
+    #[error("Clipboard read-back isn't supported on this system")]
+    VerificationUnsupported,
+}
+
+/// Copies text to clipboard, optionally reading it back to verify the copy
+/// actually landed. Some platforms/backends report success from `set_text`
+/// even when the clipboard silently didn't update, which otherwise leaves
+/// the user pasting stale content.
+///
+/// If `clipboard_command` is configured (see `kookie config set
+/// clipboard_command`), that external command is used instead of the
+/// native `arboard` backend - useful on setups (e.g. some tiling window
+/// managers) where `arboard` can't find a clipboard to talk to but a tool
+/// like `wl-copy` works. `verify` has no effect on that path: there's no
+/// portable way to read a value back from an arbitrary external command.
+pub fn copy_to_clipboard(text: &str, verify: bool) -> Result<(), ClipboardError> {
+    if let Some(cmd) = crate::session::cache::load_config().clipboard_command {
+        return run_external_command(&cmd, text);
+    }
+
+    let mut clipboard = Clipboard::new().map_err(|_| ClipboardError::Unavailable)?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| ClipboardError::Other(e.to_string()))?;
+
+    #[cfg(windows)]
+    if crate::session::cache::load_config().clipboard_history_protection {
+        windows_history::exclude_from_history();
+    }
+
+    if verify {
+        match clipboard.get_text() {
+            Ok(read_back) if read_back == text => {}
+            Ok(_) => return Err(ClipboardError::VerificationMismatch),
+            Err(_) => return Err(ClipboardError::VerificationUnsupported),
+        }
+    }
 
-/// Copies text to clipboard
-pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
-    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
-    clipboard.set_text(text).map_err(|e| e.to_string())?;
     Ok(())
 }
+
+/// Pipes `text` into `cmd`'s stdin. `cmd` is split on whitespace into a
+/// program and its arguments (e.g. `"xclip -selection clipboard"`) - no
+/// shell is involved, so quoting rules don't apply, but the secret itself
+/// is never part of `cmd` and never touches argv, only stdin.
+fn run_external_command(cmd: &str, text: &str) -> Result<(), ClipboardError> {
+    let mut parts = cmd.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| ClipboardError::Other("clipboard_command is empty".to_string()))?;
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| ClipboardError::Other(format!("failed to run '{}': {}", cmd, e)))?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| ClipboardError::Other("failed to open stdin".to_string()))?
+        .write_all(text.as_bytes())
+        .map_err(|e| ClipboardError::Other(e.to_string()))?;
+
+    let status = child.wait().map_err(|e| ClipboardError::Other(e.to_string()))?;
+    if !status.success() {
+        return Err(ClipboardError::Other(format!("'{}' exited with {}", cmd, status)));
+    }
+
+    Ok(())
+}
+
+/// Probes whether a clipboard backend is available on this system
+///
+/// Useful on headless/SSH environments where there is no display server
+/// and clipboard access would otherwise fail with a cryptic error.
+pub fn is_available() -> bool {
+    Clipboard::new().is_ok()
+}
+
+/// Marks clipboard contents as excluded from Windows 10+ Clipboard History
+/// and Cloud Clipboard sync, so a copied secret doesn't end up persisted
+/// or synced across devices. Opt out via `kookie config set
+/// clipboard_history_protection false` for users who rely on history.
+#[cfg(windows)]
+mod windows_history {
+    use clipboard_win::raw;
+
+    pub fn exclude_from_history() {
+        // Presence of this format (content is ignored) tells Windows to
+        // drop the clipboard contents from History/Cloud Clipboard
+        // processing entirely. `CanIncludeInClipboardHistory` /
+        // `CanUploadToCloudClipboard` are set to a DWORD 0 as a second,
+        // more specific signal some Windows builds check instead.
+        if let Some(format) = clipboard_win::register_format("ExcludeClipboardContentFromMonitorProcessing") {
+            let _ = raw::set_without_clear(format.get(), &[1u8]);
+        }
+        let zero = 0u32.to_ne_bytes();
+        if let Some(format) = clipboard_win::register_format("CanIncludeInClipboardHistory") {
+            let _ = raw::set_without_clear(format.get(), &zero);
+        }
+        if let Some(format) = clipboard_win::register_format("CanUploadToCloudClipboard") {
+            let _ = raw::set_without_clear(format.get(), &zero);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_external_command_receives_value_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_path = dir.path().join("clipboard-output");
+
+        run_external_command(&format!("tee {}", out_path.display()), "s3cret-value").unwrap();
+
+        assert_eq!(std::fs::read_to_string(out_path).unwrap(), "s3cret-value");
+    }
+}
@@ -1,44 +1,278 @@
 //! Display utilities for formatting output
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use crate::vault::types::*;
 use colored::*;
+use std::sync::OnceLock;
 
-/// Prints a success message
-pub fn success(msg: &str) {
-    println!("{} {}", "✓".green().bold(), msg);
+/// How much non-error output the display functions should produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    /// Only errors and essential output (suitable for scripting)
+    Quiet,
+    /// Errors, successes, info and warnings (default)
+    Normal,
+    /// Normal, plus diagnostic lines (file paths, session expiry, timings)
+    Verbose,
 }
 
-/// Prints an error message
-pub fn error(msg: &str) {
-    println!("{} {}", "✗".red().bold(), msg);
+/// How much of a secret value `display_*` functions print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevealMode {
+    /// Fully masked (`••••••••`)
+    Hidden,
+    /// Last 4 characters shown (`••••••••3f9a`); fully masked under 8 chars
+    Partial,
+    /// The raw value
+    #[default]
+    Full,
+}
+
+impl std::str::FromStr for RevealMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hidden" => Ok(RevealMode::Hidden),
+            "partial" => Ok(RevealMode::Partial),
+            "full" => Ok(RevealMode::Full),
+            other => Err(format!("Unknown reveal mode '{}'. Expected one of: hidden, partial, full", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for RevealMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RevealMode::Hidden => "hidden",
+            RevealMode::Partial => "partial",
+            RevealMode::Full => "full",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How `kookie get --encoding` should re-encode a secret's stored value
+/// before displaying or copying it. Handy for a generated key that needs
+/// to be consumed in a different format than how it was stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// The value as stored, unmodified (the existing behavior)
+    #[default]
+    Utf8,
+    /// The value's bytes, lowercase-hex-encoded
+    Hex,
+    /// The value's bytes, standard base64-encoded
+    Base64,
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf8" => Ok(Encoding::Utf8),
+            "hex" => Ok(Encoding::Hex),
+            "base64" => Ok(Encoding::Base64),
+            other => Err(format!("Unknown encoding '{}'. Expected one of: hex, base64, utf8", other)),
+        }
+    }
+}
+
+/// Re-encodes `value`'s raw bytes per `encoding`. `Encoding::Utf8` is a
+/// no-op, returning `value` unchanged.
+pub fn encode_value(value: &str, encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Utf8 => value.to_string(),
+        Encoding::Hex => value.as_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
+        Encoding::Base64 => BASE64.encode(value.as_bytes()),
+    }
+}
+
+static LOG_LEVEL: OnceLock<LogLevel> = OnceLock::new();
+
+/// Sets the global log level. Intended to be called once, early in `main`,
+/// from the `--quiet`/`--verbose` CLI flags.
+pub fn set_log_level(level: LogLevel) {
+    let _ = LOG_LEVEL.set(level);
+}
+
+fn log_level() -> LogLevel {
+    *LOG_LEVEL.get().unwrap_or(&LogLevel::Normal)
+}
+
+/// Prints a success message. Suppressed at `Quiet`.
+pub fn success(msg: &str) {
+    if log_level() == LogLevel::Quiet {
+        return;
+    }
+    println!("{} {}", "✓".green().bold(), msg);
 }
 
-/// Prints a warning message
+/// Prints a warning message. Suppressed at `Quiet`.
 pub fn warning(msg: &str) {
+    if log_level() == LogLevel::Quiet {
+        return;
+    }
     println!("{} {}", "!".yellow().bold(), msg);
 }
 
-/// Prints an info message
+/// Prints a failing diagnostic check. Always printed, regardless of log
+/// level. Distinct from `warning` - used where something is outright broken
+/// rather than merely worth a heads-up (e.g. `kookie doctor`).
+pub fn fail(msg: &str) {
+    println!("{} {}", "✗".red().bold(), msg);
+}
+
+/// Prints an info message. Suppressed at `Quiet`.
 pub fn info(msg: &str) {
+    if log_level() == LogLevel::Quiet {
+        return;
+    }
     println!("{} {}", "ℹ".blue().bold(), msg);
 }
 
+/// Prints a diagnostic line (e.g. which file was read, session expiry, KDF
+/// timing). Only printed at `Verbose`.
+pub fn verbose(msg: &str) {
+    if log_level() == LogLevel::Verbose {
+        println!("{} {}", "»".dimmed(), msg.dimmed());
+    }
+}
+
+/// Longest mask `mask_of_len` will print, regardless of the real value's
+/// length - an SSH private key masked at its true length would otherwise
+/// print an unreadable wall of mask characters.
+const MAX_MASK_LEN: usize = 20;
+
+/// Builds a mask string of the configured `mask_char` (default `•`) whose
+/// length reflects `len`, capped at `MAX_MASK_LEN` and never empty.
+fn mask_of_len(len: usize) -> String {
+    let mask_char = crate::session::cache::load_config().mask_char;
+    mask_char.to_string().repeat(len.clamp(1, MAX_MASK_LEN))
+}
+
 /// Prints a secret value (masked by default)
 pub fn print_secret(label: &str, value: &str, show: bool) {
     let display = if show {
         value.to_string()
     } else {
-        "••••••••".to_string()
+        mask_of_len(value.chars().count())
+    };
+    println!("  {}: {}", label.dimmed(), display.yellow());
+}
+
+/// Prints a secret value with only its last 4 characters shown (e.g.
+/// `••••••••3f9a`), enough to tell two similar secrets apart without a
+/// full reveal. Values under 8 characters reveal nothing - there isn't
+/// enough of the value left to mask for the partial reveal to be safe.
+pub fn print_secret_partial(label: &str, value: &str) {
+    let chars: Vec<char> = value.chars().collect();
+    let display = if chars.len() < 8 {
+        mask_of_len(chars.len())
+    } else {
+        let tail: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}{}", mask_of_len(chars.len() - 4), tail)
     };
     println!("  {}: {}", label.dimmed(), display.yellow());
 }
 
+/// Splits a base32 string into space-separated groups of four, the way
+/// authenticator apps print a TOTP seed during enrollment (e.g.
+/// `JBSW Y3DP EHPK 3PXP`) - much less error-prone to transcribe by hand
+/// than the raw run-together secret.
+#[allow(dead_code)]
+pub fn group_base32(secret: &str) -> String {
+    secret
+        .chars()
+        .collect::<Vec<_>>()
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Prints a secret value according to `mode`.
+pub fn print_secret_with_mode(label: &str, value: &str, mode: RevealMode) {
+    match mode {
+        RevealMode::Hidden => print_secret(label, value, false),
+        RevealMode::Partial => print_secret_partial(label, value),
+        RevealMode::Full => print_secret(label, value, true),
+    }
+}
+
+/// Prints a secret value, waits `seconds`, then erases the line so it
+/// doesn't linger in scrollback. Useful for reading a value aloud without
+/// leaving it visible afterwards.
+///
+/// On non-TTY output (e.g. piped to a file) clearing the line is
+/// meaningless, so this falls back to a normal, permanent print.
+pub fn print_secret_temporarily(label: &str, value: &str, seconds: u64) {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        print_secret(label, value, true);
+        return;
+    }
+
+    print!("  {}: {}", label.dimmed(), value.yellow());
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+
+    std::thread::sleep(std::time::Duration::from_secs(seconds));
+
+    // \r moves to the start of the line, \x1b[2K erases the entire line.
+    print!("\r\x1b[2K");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+/// Runs `f` while printing an animated `label` spinner, clearing it once
+/// `f` returns. Reassures the user during a slow blocking call (Argon2 key
+/// derivation at a high memory cost is the main one) that the tool hasn't
+/// hung.
+///
+/// On non-TTY output, or at `Quiet`, the spinner would just be noise (or
+/// worse, garbage left in a log file), so this falls back to running `f`
+/// with no output at all.
+pub fn with_spinner<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() || log_level() == LogLevel::Quiet {
+        return f();
+    }
+
+    const FRAMES: [&str; 4] = ["⠋", "⠙", "⠹", "⠸"];
+    let done = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let done_clone = done.clone();
+    let label = label.to_string();
+
+    let spinner = std::thread::spawn(move || {
+        let mut frame = 0;
+        while !done_clone.load(std::sync::atomic::Ordering::Relaxed) {
+            print!("\r{} {}", FRAMES[frame % FRAMES.len()].cyan(), label.dimmed());
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            frame += 1;
+            std::thread::sleep(std::time::Duration::from_millis(80));
+        }
+    });
+
+    let result = f();
+
+    done.store(true, std::sync::atomic::Ordering::Relaxed);
+    let _ = spinner.join();
+    print!("\r\x1b[2K");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+
+    result
+}
+
 /// Formats a password for display
-pub fn display_password(password: &Password, show_secret: bool) {
+pub fn display_password(password: &Password, mode: RevealMode) {
     println!();
     println!("{}", "═".repeat(50).dimmed());
     println!("{} {}", "ID:".dimmed(), password.id.cyan());
-    println!("{} {}", "Name:".dimmed(), password.name.white().bold());
+    let star = if password.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), password.name.white().bold());
     
     if let Some(desc) = &password.description {
         println!("{} {}", "Description:".dimmed(), desc);
@@ -47,22 +281,34 @@ pub fn display_password(password: &Password, show_secret: bool) {
         println!("{} {}", "Username:".dimmed(), username.green());
     }
     
-    print_secret("Password", &password.password, show_secret);
+    print_secret_with_mode("Password", &password.password, mode);
     
     if let Some(url) = &password.url {
         println!("{} {}", "URL:".dimmed(), url.blue().underline());
     }
-    
+    if let Some(notes) = &password.notes {
+        println!("{} {}", "Notes:".dimmed(), notes);
+    }
+    if let Some(days) = password.rotate_after_days {
+        let status = if password.is_due_for_rotation() { "due for rotation".red() } else { "ok".green() };
+        println!("{} every {} days ({})", "Rotation:".dimmed(), days, status);
+    }
+    if let Some(expires) = password.expires_at {
+        let status = if password.is_expired() { "EXPIRED".red() } else { "valid".green() };
+        println!("{} {} ({})", "Expires:".dimmed(), expires.format("%Y-%m-%d %H:%M"), status);
+    }
+
     println!("{} {}", "Created:".dimmed(), password.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
 /// Formats an API key for display
-pub fn display_api_key(api_key: &ApiKey, show_secret: bool) {
+pub fn display_api_key(api_key: &ApiKey, mode: RevealMode) {
     println!();
     println!("{}", "═".repeat(50).dimmed());
     println!("{} {}", "ID:".dimmed(), api_key.id.cyan());
-    println!("{} {}", "Name:".dimmed(), api_key.name.white().bold());
+    let star = if api_key.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), api_key.name.white().bold());
     
     if let Some(desc) = &api_key.description {
         println!("{} {}", "Description:".dimmed(), desc);
@@ -71,36 +317,52 @@ pub fn display_api_key(api_key: &ApiKey, show_secret: bool) {
         println!("{} {}", "Service:".dimmed(), service.green());
     }
     
-    print_secret("Key", &api_key.key, show_secret);
-    
+    print_secret_with_mode("Key", &api_key.key, mode);
+
+    if let Some(expires) = api_key.expires_at {
+        let status = if api_key.is_expired() { "EXPIRED".red() } else { "valid".green() };
+        println!("{} {} ({})", "Expires:".dimmed(), expires.format("%Y-%m-%d %H:%M"), status);
+    }
+    if let Some(notes) = &api_key.notes {
+        println!("{} {}", "Notes:".dimmed(), notes);
+    }
+
     println!("{} {}", "Created:".dimmed(), api_key.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
 /// Formats a note for display
-pub fn display_note(note: &Note, show_content: bool) {
+pub fn display_note(note: &Note, mode: RevealMode) {
     println!();
     println!("{}", "═".repeat(50).dimmed());
     println!("{} {}", "ID:".dimmed(), note.id.cyan());
-    println!("{} {}", "Name:".dimmed(), note.name.white().bold());
-    
-    if show_content {
-        println!("{}", "Content:".dimmed());
-        println!("{}", note.content.yellow());
-    } else {
-        println!("{} {}", "Content:".dimmed(), "••••••••".yellow());
+    let star = if note.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), note.name.white().bold());
+
+    match mode {
+        RevealMode::Full => {
+            println!("{}", "Content:".dimmed());
+            println!("{}", note.content.yellow());
+        }
+        RevealMode::Partial | RevealMode::Hidden => print_secret_with_mode("Content", &note.content, mode),
     }
-    
+
+    if !note.attachments.is_empty() {
+        let names: Vec<&str> = note.attachments.iter().map(|a| a.filename.as_str()).collect();
+        println!("{} {}", "Attachments:".dimmed(), names.join(", "));
+    }
+
     println!("{} {}", "Created:".dimmed(), note.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
 /// Formats a database credential for display
-pub fn display_db_credential(cred: &DbCredential, show_secret: bool) {
+pub fn display_db_credential(cred: &DbCredential, mode: RevealMode) {
     println!();
     println!("{}", "═".repeat(50).dimmed());
     println!("{} {}", "ID:".dimmed(), cred.id.cyan());
-    println!("{} {}", "Name:".dimmed(), cred.name.white().bold());
+    let star = if cred.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), cred.name.white().bold());
     
     if let Some(desc) = &cred.description {
         println!("{} {}", "Description:".dimmed(), desc);
@@ -108,7 +370,10 @@ pub fn display_db_credential(cred: &DbCredential, show_secret: bool) {
     if let Some(db_type) = &cred.db_type {
         println!("{} {}", "Type:".dimmed(), db_type.green());
     }
-    
+    if let Some(environment) = &cred.environment {
+        println!("{} {}", "Environment:".dimmed(), environment.magenta());
+    }
+
     println!("{} {}", "Host:".dimmed(), cred.host);
     if let Some(port) = cred.port {
         println!("{} {}", "Port:".dimmed(), port);
@@ -116,22 +381,26 @@ pub fn display_db_credential(cred: &DbCredential, show_secret: bool) {
     println!("{} {}", "Database:".dimmed(), cred.database);
     println!("{} {}", "Username:".dimmed(), cred.username.green());
     
-    print_secret("Password", &cred.password, show_secret);
-    
-    if show_secret {
+    print_secret_with_mode("Password", &cred.password, mode);
+
+    if mode == RevealMode::Full {
         println!("{} {}", "Connection String:".dimmed(), cred.connection_string().blue());
     }
-    
+    if let Some(notes) = &cred.notes {
+        println!("{} {}", "Notes:".dimmed(), notes);
+    }
+
     println!("{} {}", "Created:".dimmed(), cred.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
 /// Formats a token for display
-pub fn display_token(token: &Token, show_secret: bool) {
+pub fn display_token(token: &Token, mode: RevealMode) {
     println!();
     println!("{}", "═".repeat(50).dimmed());
     println!("{} {}", "ID:".dimmed(), token.id.cyan());
-    println!("{} {}", "Name:".dimmed(), token.name.white().bold());
+    let star = if token.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), token.name.white().bold());
     
     if let Some(desc) = &token.description {
         println!("{} {}", "Description:".dimmed(), desc);
@@ -140,7 +409,7 @@ pub fn display_token(token: &Token, show_secret: bool) {
         println!("{} {}", "Type:".dimmed(), token_type.green());
     }
     
-    print_secret("Token", &token.token, show_secret);
+    print_secret_with_mode("Token", &token.token, mode);
     
     if let Some(expires) = token.expires_at {
         let status = if token.is_expired() {
@@ -150,11 +419,68 @@ pub fn display_token(token: &Token, show_secret: bool) {
         };
         println!("{} {} ({})", "Expires:".dimmed(), expires.format("%Y-%m-%d %H:%M"), status);
     }
-    
+    if let Some(notes) = &token.notes {
+        println!("{} {}", "Notes:".dimmed(), notes);
+    }
+
     println!("{} {}", "Created:".dimmed(), token.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
+/// Formats a custom secret for display, masking fields marked `secret`
+pub fn display_custom(custom: &Custom, mode: RevealMode) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), custom.id.cyan());
+    let star = if custom.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), custom.name.white().bold());
+
+    if let Some(desc) = &custom.description {
+        println!("{} {}", "Description:".dimmed(), desc);
+    }
+
+    for field in &custom.fields {
+        if field.secret {
+            print_secret_with_mode(&field.name, &field.value, mode);
+        } else {
+            println!("{} {}", format!("{}:", field.name).dimmed(), field.value);
+        }
+    }
+
+    println!("{} {}", "Created:".dimmed(), custom.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
+/// Formats an SSH key pair for display, masking the private key by default
+pub fn display_ssh_key(ssh_key: &SshKey, mode: RevealMode) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), ssh_key.id.cyan());
+    let star = if ssh_key.favorite { "★ " } else { "" };
+    println!("{} {}{}", "Name:".dimmed(), star.yellow(), ssh_key.name.white().bold());
+
+    if let Some(desc) = &ssh_key.description {
+        println!("{} {}", "Description:".dimmed(), desc);
+    }
+    println!("{} {}", "Type:".dimmed(), ssh_key.key_type.green());
+
+    println!("{} {}", "Public Key:".dimmed(), ssh_key.public_key);
+    print_secret_with_mode("Private Key", &ssh_key.private_key, mode);
+
+    if ssh_key.passphrase.is_some() {
+        println!("{} {}", "Passphrase:".dimmed(), "set".green());
+    }
+    if let Some(comment) = &ssh_key.comment {
+        println!("{} {}", "Comment:".dimmed(), comment);
+    }
+    if let Some(notes) = &ssh_key.notes {
+        println!("{} {}", "Notes:".dimmed(), notes);
+    }
+
+    println!("{} {}", "Created:".dimmed(), ssh_key.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
 /// Prints a list header
 pub fn list_header(secret_type: &str, count: usize) {
     println!();
@@ -162,12 +488,132 @@ pub fn list_header(secret_type: &str, count: usize) {
     println!("{}", "─".repeat(50).dimmed());
 }
 
-/// Prints a list item summary
-pub fn list_item(id: &str, name: &str, extra: Option<&str>) {
+/// Prints a list item summary. `favorite` prefixes the line with a ★ marker.
+/// `expired` prints the `extra` annotation in red instead of dimmed (used by
+/// `kookie list --tokens --sort expiry` to flag already-expired tokens).
+pub fn list_item(id: &str, name: &str, extra: Option<&str>, favorite: bool, expired: bool) {
     print!("  {} ", "•".dimmed());
+    if favorite {
+        print!("{} ", "★".yellow());
+    }
     print!("{} ", name.white().bold());
     if let Some(e) = extra {
-        print!("{} ", format!("({})", e).dimmed());
+        let annotation = format!("({})", e);
+        print!("{} ", if expired { annotation.red() } else { annotation.dimmed() });
     }
     println!("{}", format!("[{}]", &id[..8]).cyan().dimmed());
 }
+
+/// One row of `kookie list --format table`
+pub struct TableRow {
+    pub name: String,
+    pub secret_type: &'static str,
+    pub username_or_service: String,
+    pub created_at: String,
+    pub tags: String,
+    pub favorite: bool,
+}
+
+/// Terminal width in columns, used by `print_table` to decide how hard to
+/// truncate long values. Falls back to 80 if it can't be determined (e.g.
+/// output piped to a file, with no `COLUMNS` in the environment).
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse::<usize>().ok())
+        .filter(|&w| w > 0)
+        .unwrap_or(80)
+}
+
+/// Truncates `s` to at most `max` characters, replacing the last one with
+/// an ellipsis if anything was cut.
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut truncated: String = s.chars().take(max.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn format_table_row(
+    name: &str,
+    secret_type: &str,
+    extra: &str,
+    created: &str,
+    tags: &str,
+    favorite: bool,
+    widths: (usize, usize, usize, usize, usize),
+) -> String {
+    let (name_w, type_w, extra_w, created_w, tags_w) = widths;
+    let star = if favorite { "★ " } else { "  " };
+    format!(
+        "{}{:<name_w$}  {:<type_w$}  {:<extra_w$}  {:<created_w$}  {:<tags_w$}",
+        star,
+        truncate(name, name_w),
+        secret_type,
+        truncate(extra, extra_w),
+        created,
+        tags,
+        name_w = name_w,
+        type_w = type_w,
+        extra_w = extra_w,
+        created_w = created_w,
+        tags_w = tags_w,
+    )
+}
+
+/// Prints `rows` as aligned columns (Name, Type, Username/Service, Created,
+/// Tags). Long values are truncated with an ellipsis so the table still
+/// fits the terminal width. Assumes `rows` is non-empty; callers print a
+/// "no secrets found" message instead when there's nothing to show.
+pub fn print_table(rows: &[TableRow]) {
+    const MIN_NAME_WIDTH: usize = 8;
+    const MIN_EXTRA_WIDTH: usize = 8;
+    const GAPS: usize = 4 * 2; // 2-space gap between each of the 5 columns
+
+    let type_w = "Type".len().max(rows.iter().map(|r| r.secret_type.len()).max().unwrap_or(0));
+    let created_w = "Created".len().max(rows.iter().map(|r| r.created_at.len()).max().unwrap_or(0));
+    let tags_w = "Tags".len().max(rows.iter().map(|r| r.tags.len()).max().unwrap_or(0));
+
+    let mut name_w = "Name".len().max(rows.iter().map(|r| r.name.chars().count()).max().unwrap_or(0));
+    let mut extra_w = "Username/Service"
+        .len()
+        .max(rows.iter().map(|r| r.username_or_service.chars().count()).max().unwrap_or(0));
+
+    let fixed = type_w + created_w + tags_w + GAPS;
+    let term_width = terminal_width();
+    if fixed + name_w + extra_w > term_width && term_width > fixed + MIN_NAME_WIDTH + MIN_EXTRA_WIDTH {
+        let available = term_width - fixed;
+        let natural = name_w + extra_w;
+        name_w = (available * name_w / natural).max(MIN_NAME_WIDTH);
+        extra_w = available.saturating_sub(name_w).max(MIN_EXTRA_WIDTH);
+    }
+    let widths = (name_w, type_w, extra_w, created_w, tags_w);
+
+    println!();
+    println!(
+        "{}",
+        format_table_row("Name", "Type", "Username/Service", "Created", "Tags", false, widths)
+            .white()
+            .bold()
+    );
+    println!("{}", "─".repeat(name_w + type_w + extra_w + created_w + tags_w + GAPS + 2).dimmed());
+    for row in rows {
+        println!(
+            "{}",
+            format_table_row(
+                &row.name,
+                row.secret_type,
+                &row.username_or_service,
+                &row.created_at,
+                &row.tags,
+                row.favorite,
+                widths,
+            )
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Hand-rolled parser for human-friendly durations like `--since 7d`
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Parses a duration like `30m`, `12h`, `7d`, or `2w` into a `chrono::Duration`.
+/// The number must be a non-negative integer followed by exactly one of the
+/// unit suffixes below.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+
+    if number.is_empty() || unit.is_empty() {
+        return Err(format!(
+            "Invalid duration '{}'. Expected a number followed by a unit, e.g. 30m, 12h, 7d, 2w",
+            s
+        ));
+    }
+
+    let amount: i64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration '{}': '{}' is not a valid number", s, number))?;
+
+    match unit {
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        other => Err(format!(
+            "Unknown duration unit '{}'. Expected one of: m, h, d, w",
+            other
+        )),
+    }
+}
+
+/// Parses an `add --password`/`add --api-key`/`add --token` expiry prompt
+/// like `90d` into an absolute deadline by adding it to the current time.
+/// Shares `parse_duration`'s syntax rather than accepting an absolute
+/// timestamp, since "expires 90 days from now" is what rotation policies
+/// actually want and is far less error-prone to type than an RFC3339 date.
+pub fn parse_expiry(s: &str) -> Result<DateTime<Utc>, String> {
+    Ok(Utc::now() + parse_duration(s)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_days() {
+        assert_eq!(parse_duration("7d").unwrap(), Duration::days(7));
+    }
+
+    #[test]
+    fn test_parse_duration_all_units() {
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::weeks(2));
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_missing_unit() {
+        assert!(parse_duration("7").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_unknown_unit() {
+        assert!(parse_duration("7x").is_err());
+    }
+
+    #[test]
+    fn test_parse_expiry_is_roughly_duration_from_now() {
+        let expiry = parse_expiry("7d").unwrap();
+        let expected = Utc::now() + Duration::days(7);
+        assert!((expiry - expected).num_seconds().abs() < 5);
+    }
+
+    #[test]
+    fn test_parse_expiry_rejects_invalid_duration() {
+        assert!(parse_expiry("soon").is_err());
+    }
+}
@@ -1,54 +1,233 @@
 //! Key and secret generators
 
-use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
 
-/// Generates a random key of specified length
-pub fn generate_random_key(length: usize) -> String {
+/// Base encoding used to render a generated key as text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyEncoding {
+    /// Lowercase hexadecimal
+    Hex,
+    /// Standard base64 (with padding)
+    Base64,
+    /// URL-safe base64 without padding (the historical default)
+    #[default]
+    Base64Url,
+    /// Each byte mapped 1:1 to a character, i.e. no real encoding
+    Raw,
+}
+
+impl std::str::FromStr for KeyEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hex" => Ok(KeyEncoding::Hex),
+            "base64" => Ok(KeyEncoding::Base64),
+            "base64url" => Ok(KeyEncoding::Base64Url),
+            "raw" => Ok(KeyEncoding::Raw),
+            other => Err(format!(
+                "Unknown encoding '{}'. Expected one of: hex, base64, base64url, raw",
+                other
+            )),
+        }
+    }
+}
+
+/// UUID variant generated by `generate uuid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UuidVersion {
+    /// Random (the common case; no structure, no timestamp leak)
+    #[default]
+    V4,
+    /// Unix-timestamp-prefixed, so values generated later sort later -
+    /// useful for correlation IDs used as database keys
+    V7,
+}
+
+impl std::str::FromStr for UuidVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "v4" | "4" => Ok(UuidVersion::V4),
+            "v7" | "7" => Ok(UuidVersion::V7),
+            other => Err(format!("Unknown UUID version '{}'. Expected one of: v4, v7", other)),
+        }
+    }
+}
+
+/// Generates a UUID of the given version
+pub fn generate_uuid(version: UuidVersion) -> String {
+    match version {
+        UuidVersion::V4 => uuid::Uuid::new_v4().to_string(),
+        UuidVersion::V7 => uuid::Uuid::now_v7().to_string(),
+    }
+}
+
+/// Encodes raw bytes using the given key encoding
+fn encode_bytes(bytes: &[u8], encoding: KeyEncoding) -> String {
+    match encoding {
+        KeyEncoding::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+        KeyEncoding::Base64 => STANDARD.encode(bytes),
+        KeyEncoding::Base64Url => URL_SAFE_NO_PAD.encode(bytes),
+        KeyEncoding::Raw => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Generates a random key of specified length, encoded with `encoding`
+pub fn generate_random_key(length: usize, encoding: KeyEncoding) -> String {
     let mut bytes = vec![0u8; length];
     rand::thread_rng().fill_bytes(&mut bytes);
-    URL_SAFE_NO_PAD.encode(&bytes)
+    encode_bytes(&bytes, encoding)
 }
 
 /// Generates a random key suitable for JWT secrets (256 bits)
 pub fn generate_jwt_secret() -> String {
-    generate_random_key(32) // 256 bits
+    generate_random_key(32, KeyEncoding::Base64Url) // 256 bits
 }
 
 /// Generates a random key suitable for encryption (256 bits)
 #[allow(dead_code)]
 pub fn generate_encryption_key() -> String {
-    generate_random_key(32)
+    generate_random_key(32, KeyEncoding::Base64Url)
 }
 
-/// Generates a random API key
-pub fn generate_api_key() -> String {
-    let mut bytes = [0u8; 24];
+/// Generates a random API key with the given `prefix`, followed by
+/// `length` bytes of randomness encoded as URL-safe base64 (no padding) so
+/// the result is always safe to use in a URL, header, or query string.
+pub fn generate_api_key(prefix: &str, length: usize) -> String {
+    let mut bytes = vec![0u8; length];
     rand::thread_rng().fill_bytes(&mut bytes);
-    format!("kk_{}", URL_SAFE_NO_PAD.encode(bytes))
+    format!("{}{}", prefix, URL_SAFE_NO_PAD.encode(bytes))
 }
 
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!@#$%^&*()_+-=[]{}|;:,.<>?";
+
 /// Generates a secure random password
 pub fn generate_password(length: usize, include_symbols: bool) -> String {
-    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
-    const DIGITS: &[u8] = b"0123456789";
-    const SYMBOLS: &[u8] = b"!@#$%^&*()_+-=[]{}|;:,.<>?";
-    
     let charset: Vec<u8> = if include_symbols {
         [LETTERS, DIGITS, SYMBOLS].concat()
     } else {
         [LETTERS, DIGITS].concat()
     };
-    
+
     let mut password = Vec::with_capacity(length);
     let mut rng = rand::thread_rng();
-    
+
     for _ in 0..length {
         let idx = (rng.next_u32() as usize) % charset.len();
         password.push(charset[idx]);
     }
-    
-    String::from_utf8(password).unwrap_or_else(|_| generate_random_key(length))
+
+    String::from_utf8(password).unwrap_or_else(|_| generate_random_key(length, KeyEncoding::Base64Url))
+}
+
+/// A named password generation policy: a minimum length plus required
+/// character classes, enforced by excluding characters from the charset
+/// (e.g. a numeric PIN excludes every letter and symbol) and by
+/// regenerating a class that didn't come up by chance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordPolicy {
+    pub min_len: usize,
+    #[serde(default)]
+    pub require_symbol: bool,
+    #[serde(default)]
+    pub require_digit: bool,
+    /// Characters to exclude from the generated password's charset
+    #[serde(default)]
+    pub exclude: String,
+}
+
+impl PasswordPolicy {
+    /// 16+ characters, at least one digit and one symbol - a reasonable
+    /// default for most sites.
+    pub fn strong() -> Self {
+        Self {
+            min_len: 16,
+            require_symbol: true,
+            require_digit: true,
+            exclude: String::new(),
+        }
+    }
+
+    /// A 6-digit numeric PIN: letters and symbols excluded entirely.
+    pub fn pin() -> Self {
+        Self {
+            min_len: 6,
+            require_symbol: false,
+            require_digit: true,
+            exclude: format!(
+                "{}{}",
+                String::from_utf8_lossy(LETTERS),
+                String::from_utf8_lossy(SYMBOLS)
+            ),
+        }
+    }
+
+    /// 12+ alphanumeric characters, no symbols - for sites that reject them.
+    pub fn alnum() -> Self {
+        Self {
+            min_len: 12,
+            require_symbol: false,
+            require_digit: true,
+            exclude: String::from_utf8_lossy(SYMBOLS).to_string(),
+        }
+    }
+
+    /// Looks up a built-in policy by name (`strong`, `pin`, `alnum`).
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "strong" => Some(Self::strong()),
+            "pin" => Some(Self::pin()),
+            "alnum" => Some(Self::alnum()),
+            _ => None,
+        }
+    }
+}
+
+/// Generates a password satisfying `policy`, regenerating the affected
+/// character class when chance alone didn't produce a required digit or
+/// symbol. `length` overrides `policy.min_len` if longer; the policy's
+/// minimum is always honored.
+pub fn generate_password_with_policy(length: Option<usize>, policy: &PasswordPolicy) -> String {
+    let length = length.unwrap_or(policy.min_len).max(policy.min_len).max(1);
+    let excluded: std::collections::HashSet<u8> = policy.exclude.bytes().collect();
+
+    let filtered =
+        |set: &[u8]| -> Vec<u8> { set.iter().copied().filter(|b| !excluded.contains(b)).collect() };
+
+    let letters = filtered(LETTERS);
+    let digits = filtered(DIGITS);
+    let symbols = filtered(SYMBOLS);
+
+    let mut charset: Vec<u8> = [letters.as_slice(), digits.as_slice(), symbols.as_slice()].concat();
+    if charset.is_empty() {
+        charset = DIGITS.to_vec();
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut password: Vec<u8> = (0..length)
+        .map(|_| charset[(rng.next_u32() as usize) % charset.len()])
+        .collect();
+
+    if policy.require_digit && !digits.is_empty() && !password.iter().any(|b| digits.contains(b)) {
+        let idx = (rng.next_u32() as usize) % password.len();
+        password[idx] = digits[(rng.next_u32() as usize) % digits.len()];
+    }
+
+    if policy.require_symbol && !symbols.is_empty() && !password.iter().any(|b| symbols.contains(b)) {
+        let idx = (rng.next_u32() as usize) % password.len();
+        password[idx] = symbols[(rng.next_u32() as usize) % symbols.len()];
+    }
+
+    String::from_utf8(password).unwrap_or_else(|_| generate_random_key(length, KeyEncoding::Base64Url))
 }
 
 #[cfg(test)]
@@ -57,11 +236,35 @@ mod tests {
 
     #[test]
     fn test_generate_random_key_length() {
-        let key = generate_random_key(32);
+        let key = generate_random_key(32, KeyEncoding::Base64Url);
         // Base64 encoded 32 bytes should be about 43 characters
         assert!(key.len() >= 40);
     }
 
+    #[test]
+    fn test_generate_random_key_hex_length() {
+        let key = generate_random_key(16, KeyEncoding::Hex);
+        assert_eq!(key.len(), 32); // 2 hex chars per byte
+    }
+
+    #[test]
+    fn test_generate_random_key_base64_length() {
+        let key = generate_random_key(16, KeyEncoding::Base64);
+        assert_eq!(key.len(), 24); // padded base64: ceil(16/3)*4
+    }
+
+    #[test]
+    fn test_generate_random_key_base64url_length() {
+        let key = generate_random_key(16, KeyEncoding::Base64Url);
+        assert_eq!(key.len(), 22); // unpadded: ceil(16*4/3)
+    }
+
+    #[test]
+    fn test_generate_random_key_raw_length() {
+        let key = generate_random_key(16, KeyEncoding::Raw);
+        assert_eq!(key.chars().count(), 16); // one char per byte
+    }
+
     #[test]
     fn test_generate_jwt_secret() {
         let secret = generate_jwt_secret();
@@ -70,13 +273,57 @@ mod tests {
 
     #[test]
     fn test_generate_api_key_prefix() {
-        let key = generate_api_key();
+        let key = generate_api_key("kk_", 24);
         assert!(key.starts_with("kk_"));
     }
 
+    #[test]
+    fn test_generate_uuid_v4_and_v7_are_valid_and_distinct() {
+        let v4 = generate_uuid(UuidVersion::V4);
+        let v7 = generate_uuid(UuidVersion::V7);
+        assert!(uuid::Uuid::parse_str(&v4).is_ok());
+        assert!(uuid::Uuid::parse_str(&v7).is_ok());
+        assert_ne!(v4, v7);
+    }
+
+    #[test]
+    fn test_generate_api_key_custom_prefix() {
+        let key = generate_api_key("sk_", 40);
+        assert!(key.starts_with("sk_"));
+    }
+
     #[test]
     fn test_generate_password_length() {
         let password = generate_password(16, true);
         assert_eq!(password.len(), 16);
     }
+
+    #[test]
+    fn test_pin_policy_is_digits_only() {
+        let pin = generate_password_with_policy(None, &PasswordPolicy::pin());
+        assert_eq!(pin.len(), 6);
+        assert!(pin.bytes().all(|b| b.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_alnum_policy_excludes_symbols() {
+        let password = generate_password_with_policy(Some(20), &PasswordPolicy::alnum());
+        assert_eq!(password.len(), 20);
+        assert!(password.bytes().all(|b| b.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn test_strong_policy_guarantees_digit_and_symbol() {
+        for _ in 0..20 {
+            let password = generate_password_with_policy(None, &PasswordPolicy::strong());
+            assert_eq!(password.len(), 16);
+            assert!(password.bytes().any(|b| b.is_ascii_digit()));
+            assert!(password.bytes().any(|b| SYMBOLS.contains(&b)));
+        }
+    }
+
+    #[test]
+    fn test_built_in_unknown_policy_is_none() {
+        assert!(PasswordPolicy::built_in("nonexistent").is_none());
+    }
 }
@@ -1,29 +1,164 @@
 //! Input utilities for interactive prompts
+//!
+//! `rpassword::read_password` disables terminal echo for the duration of a
+//! read and restores it via a `Drop` guard when the read returns. That
+//! guard never runs if the read is interrupted by Ctrl-C: the default
+//! SIGINT disposition kills the process immediately, skipping Rust
+//! destructors, and the shell is left with echo disabled until the user
+//! runs `reset`/`stty echo` by hand. `echo_guard` (Unix only) works around
+//! this by installing a SIGINT handler for the duration of each hidden-input
+//! prompt that restores the terminal itself before letting the signal kill
+//! the process as usual.
 
 use colored::*;
 use rpassword::read_password;
+use std::fs;
 use std::io::{self, Write};
+use std::sync::OnceLock;
 
-/// Prompts for a password (hidden input)
+static AUTO_CONFIRM: OnceLock<bool> = OnceLock::new();
+
+/// Enables the global `--yes`/`-y` override, making `prompt_confirm` return
+/// the affirmative immediately instead of blocking on stdin. Intended to be
+/// called once, early in `main`, from the `--yes` global flag.
+pub fn set_auto_confirm(yes: bool) {
+    let _ = AUTO_CONFIRM.set(yes);
+}
+
+fn auto_confirm() -> bool {
+    *AUTO_CONFIRM.get().unwrap_or(&false)
+}
+
+#[cfg(unix)]
+mod echo_guard {
+    use libc::{c_int, sighandler_t, tcsetattr, termios, SIGINT, TCSANOW};
+    use std::io;
+    use std::mem::MaybeUninit;
+
+    const STDIN_FD: c_int = 0;
+
+    // Single-threaded by construction: prompts run one at a time on the
+    // main thread, so there's never more than one guard armed and no need
+    // for synchronization beyond "write before arming the handler, read
+    // only while it's armed".
+    static mut ORIG_TERMIOS: MaybeUninit<termios> = MaybeUninit::uninit();
+    static mut PREV_HANDLER: sighandler_t = 0;
+
+    extern "C" fn restore_and_reraise(sig: c_int) {
+        unsafe {
+            let orig = std::ptr::addr_of!(ORIG_TERMIOS);
+            tcsetattr(STDIN_FD, TCSANOW, (*orig).as_ptr());
+            libc::signal(SIGINT, PREV_HANDLER);
+            libc::raise(sig);
+        }
+    }
+
+    /// Restores the terminal's current echo/raw state if Ctrl-C interrupts
+    /// a hidden-input read, before the process exits. Disarmed on drop,
+    /// restoring whatever SIGINT handler was previously installed.
+    pub struct EchoGuard {
+        armed: bool,
+    }
+
+    impl EchoGuard {
+        pub fn arm() -> io::Result<Self> {
+            unsafe {
+                let mut term = MaybeUninit::<termios>::uninit();
+                if libc::tcgetattr(STDIN_FD, term.as_mut_ptr()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                ORIG_TERMIOS = MaybeUninit::new(term.assume_init());
+                PREV_HANDLER = libc::signal(SIGINT, restore_and_reraise as *const () as sighandler_t);
+            }
+            Ok(EchoGuard { armed: true })
+        }
+    }
+
+    impl Drop for EchoGuard {
+        fn drop(&mut self) {
+            if self.armed {
+                unsafe {
+                    libc::signal(SIGINT, PREV_HANDLER);
+                }
+            }
+        }
+    }
+}
+
+/// Resolves a secret value passed via a CLI flag, without accepting the
+/// literal secret as a plain argument when avoidable.
+///
+/// - `-` reads a single line from stdin
+/// - `@path` reads the full contents of `path` (trimmed)
+/// - anything else is returned as-is
+///
+/// Passing secrets as literal flag values is discouraged: they land in
+/// shell history and process listings. Prefer `--value -` or `--value
+/// @path` so the value never appears on the command line.
+pub fn resolve_value(arg: &str) -> io::Result<String> {
+    if arg == "-" {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(input.trim_end_matches(['\n', '\r']).to_string())
+    } else if let Some(path) = arg.strip_prefix('@') {
+        let content = fs::read_to_string(path)?;
+        Ok(content.trim_end_matches(['\n', '\r']).to_string())
+    } else {
+        Ok(arg.to_string())
+    }
+}
+
+/// Prompts for a password (hidden input). On Unix, a Ctrl-C during the read
+/// restores the terminal's echo state before the process exits - see the
+/// module docs and `echo_guard`.
 pub fn prompt_password(prompt: &str) -> io::Result<String> {
     print!("{} ", prompt.cyan());
     io::stdout().flush()?;
+
+    #[cfg(unix)]
+    let _guard = echo_guard::EchoGuard::arm().ok();
+
     read_password()
 }
 
 /// Prompts for text input
 pub fn prompt_text(prompt: &str) -> io::Result<String> {
-    print!("{} ", prompt.cyan());
+    prompt_text_with_default(prompt, None)
+}
+
+/// Prompts for text input, showing `default` in brackets after the prompt
+/// and returning it verbatim on empty input. Used by `add_*` to pre-fill
+/// the team-configured hints in `SessionConfig::prompts` (see `kookie
+/// config`).
+pub fn prompt_text_with_default(prompt: &str, default: Option<&str>) -> io::Result<String> {
+    match default {
+        Some(d) => print!("{} {} ", prompt.cyan(), format!("[{}]", d).dimmed()),
+        None => print!("{} ", prompt.cyan()),
+    }
     io::stdout().flush()?;
-    
+
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
-    Ok(input.trim().to_string())
+    let input = input.trim().to_string();
+
+    if input.is_empty() {
+        if let Some(d) = default {
+            return Ok(d.to_string());
+        }
+    }
+    Ok(input)
 }
 
 /// Prompts for optional text input
 pub fn prompt_optional(prompt: &str) -> io::Result<Option<String>> {
-    let input = prompt_text(prompt)?;
+    prompt_optional_with_default(prompt, None)
+}
+
+/// Prompts for optional text input, showing `default` in brackets and
+/// returning it on empty input instead of `None`. See
+/// `prompt_text_with_default`.
+pub fn prompt_optional_with_default(prompt: &str, default: Option<&str>) -> io::Result<Option<String>> {
+    let input = prompt_text_with_default(prompt, default)?;
     if input.is_empty() {
         Ok(None)
     } else {
@@ -31,8 +166,16 @@ pub fn prompt_optional(prompt: &str) -> io::Result<Option<String>> {
     }
 }
 
-/// Prompts for confirmation (y/n)
+/// Prompts for confirmation (y/n). Under the global `--yes` override, skips
+/// the prompt entirely and returns the affirmative - callers still print
+/// what they did, so `--yes` stays auditable rather than silent.
 pub fn prompt_confirm(prompt: &str, default: bool) -> io::Result<bool> {
+    if auto_confirm() {
+        let suffix = if default { "[Y/n]" } else { "[y/N]" };
+        println!("{} {} {}", prompt.cyan(), suffix.dimmed(), "y (--yes)".dimmed());
+        return Ok(true);
+    }
+
     let suffix = if default { "[Y/n]" } else { "[y/N]" };
     print!("{} {} ", prompt.cyan(), suffix.dimmed());
     io::stdout().flush()?;
@@ -69,6 +212,25 @@ pub fn prompt_new_password(prompt: &str) -> io::Result<String> {
     }
 }
 
+/// Prompts for a secret value twice and requires both entries to match,
+/// re-prompting on mismatch. Unlike `prompt_new_password`, this doesn't
+/// enforce a minimum length - it's meant for arbitrary secret values (API
+/// keys, tokens, DB passwords) pasted blind, where the only risk is a typo,
+/// not a weak value.
+pub fn prompt_password_confirmed(prompt: &str) -> io::Result<String> {
+    loop {
+        let value = prompt_password(prompt)?;
+        let confirm = prompt_password("Confirm (re-enter to verify):")?;
+
+        if value != confirm {
+            println!("{}", "Entries did not match. Try again.".red());
+            continue;
+        }
+
+        return Ok(value);
+    }
+}
+
 /// Prompts for a number
 #[allow(dead_code)]
 pub fn prompt_number(prompt: &str, default: Option<u32>) -> io::Result<u32> {
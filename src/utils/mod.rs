@@ -1,6 +1,8 @@
 //! Utility modules
 
 pub mod clipboard;
+pub mod duration;
 pub mod generators;
 pub mod input;
 pub mod display;
+pub mod secure_fs;
@@ -0,0 +1,43 @@
+//! Writing sensitive files without a window where they're readable
+//!
+//! `fs::write` followed by a separate `set_permissions(0o600)` call leaves
+//! the file sitting under the process umask (commonly `0644`) between
+//! those two calls - a crash or a concurrent reader in that window sees
+//! a world/group-readable vault, session, or exported secret. `write`
+//! creates the file with the restrictive mode from the moment it exists,
+//! closing that window, instead of tightening it after the fact.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes `contents` to `path`, creating (or truncating) it with mode
+/// `0600` from the start. Unix only; on other platforms this is a plain
+/// `fs::write` - there's no portable equivalent of `OpenOptions::mode`.
+pub fn write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_with_mode(path, contents, 0o600)
+}
+
+/// Like [`write`], but with an explicit mode - for files like an extracted
+/// SSH public key that are meant to be world-readable (`0644`) rather than
+/// `0600`.
+#[cfg_attr(not(unix), allow(unused_variables))]
+pub fn write_with_mode(path: &Path, contents: &[u8], mode: u32) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(mode)
+            .open(path)?;
+        file.write_all(contents)
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,205 @@
+//! Git-backed vault versioning
+//!
+//! `kookie git init` turns the vault directory into a git repository, and
+//! once `git_autocommit` is enabled in config, `Vault::save`/`Vault::mutate`
+//! commit after every change instead of the caller having to remember to.
+//! `kookie git log`/`kookie git restore <commit>` read that history back.
+//! The committed `vault.json` is exactly the encrypted blob already on
+//! disk - nothing extra is decrypted or exposed for this - so the history
+//! is safe to push to a private remote.
+//!
+//! Requires the `git` feature. Without it, the explicit `kookie git *`
+//! commands return a clear error; `autocommit` silently no-ops instead,
+//! since a vault that never ran `kookie git init` (the overwhelming
+//! majority) shouldn't see a warning on every single save.
+
+use super::VaultError;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+/// One entry in `kookie git log`'s output.
+pub struct LogEntry {
+    pub short_hash: String,
+    pub message: String,
+    pub committed_at: DateTime<Utc>,
+}
+
+/// Turns `vault_dir` into a git repository (if it isn't one already),
+/// writes its `.gitignore`, and commits whatever's already there.
+pub fn init(vault_dir: &Path) -> Result<(), VaultError> {
+    backend::init(vault_dir)
+}
+
+/// Commits `vault_dir`'s current state with `message`, if `vault_dir` is a
+/// git repository (i.e. `kookie git init` has run). A no-op otherwise, so
+/// every `Vault::save` can call this unconditionally.
+pub fn autocommit(vault_dir: &Path, message: &str) {
+    if let Err(e) = backend::autocommit(vault_dir, message) {
+        crate::utils::display::warning(&format!("git auto-commit failed: {}", e));
+    }
+}
+
+/// Returns `vault_dir`'s commit history, newest first.
+pub fn log(vault_dir: &Path) -> Result<Vec<LogEntry>, VaultError> {
+    backend::log(vault_dir)
+}
+
+/// Checks out `vault.json` as it was at `commit` (a hash, prefix, or other
+/// git revision), overwriting the current one. Nothing else in the
+/// working tree is touched, and nothing is re-committed - run `kookie
+/// unlock` afterward to confirm the restored vault opens as expected.
+pub fn restore(vault_dir: &Path, commit: &str) -> Result<(), VaultError> {
+    backend::restore(vault_dir, commit)
+}
+
+#[cfg(feature = "git")]
+mod backend {
+    use super::LogEntry;
+    use crate::vault::VaultError;
+    use chrono::{TimeZone, Utc};
+    use git2::{Commit, IndexAddOption, Repository, Signature};
+    use std::path::Path;
+
+    /// `init` writes this as the vault directory's `.gitignore`.
+    /// Session/lock state is transient, and `config.json` can hold
+    /// plaintext sync credentials, so neither belongs in history even
+    /// though `vault.json` itself is encrypted.
+    const GITIGNORE: &str = "\
+.session
+.pin_session
+.locked
+vault.json.lock
+config.json
+";
+
+    fn git_err(e: git2::Error) -> VaultError {
+        VaultError::GitError(e.message().to_string())
+    }
+
+    fn open(vault_dir: &Path) -> Result<Repository, VaultError> {
+        Repository::open(vault_dir).map_err(|_| {
+            VaultError::GitError(format!(
+                "'{}' isn't a git repository yet; run 'kookie git init' first.",
+                vault_dir.display()
+            ))
+        })
+    }
+
+    fn signature() -> Result<Signature<'static>, VaultError> {
+        Signature::now("kookie", "kookie@localhost").map_err(git_err)
+    }
+
+    pub fn init(vault_dir: &Path) -> Result<(), VaultError> {
+        let repo = match Repository::open(vault_dir) {
+            Ok(repo) => repo,
+            Err(_) => Repository::init(vault_dir).map_err(git_err)?,
+        };
+
+        let gitignore_path = vault_dir.join(".gitignore");
+        if !gitignore_path.exists() {
+            std::fs::write(&gitignore_path, GITIGNORE)?;
+        }
+
+        commit_all(&repo, "kookie git init")?;
+        Ok(())
+    }
+
+    pub fn autocommit(vault_dir: &Path, message: &str) -> Result<(), VaultError> {
+        if !vault_dir.join(".git").exists() {
+            return Ok(());
+        }
+        commit_all(&open(vault_dir)?, message)
+    }
+
+    /// Stages everything not excluded by `.gitignore` and commits it,
+    /// skipping the commit entirely if nothing actually changed.
+    fn commit_all(repo: &Repository, message: &str) -> Result<(), VaultError> {
+        let mut index = repo.index().map_err(git_err)?;
+        index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None).map_err(git_err)?;
+        index.write().map_err(git_err)?;
+
+        let tree_id = index.write_tree().map_err(git_err)?;
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+
+        if let Some(parent) = &parent {
+            if parent.tree_id() == tree_id {
+                return Ok(());
+            }
+        }
+
+        let tree = repo.find_tree(tree_id).map_err(git_err)?;
+        let sig = signature()?;
+        let parents: Vec<&Commit> = parent.iter().collect();
+
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents).map_err(git_err)?;
+        Ok(())
+    }
+
+    pub fn log(vault_dir: &Path) -> Result<Vec<LogEntry>, VaultError> {
+        let repo = open(vault_dir)?;
+        let mut revwalk = repo.revwalk().map_err(git_err)?;
+        revwalk.push_head().map_err(git_err)?;
+
+        revwalk
+            .map(|oid| {
+                let oid = oid.map_err(git_err)?;
+                let commit = repo.find_commit(oid).map_err(git_err)?;
+                let committed_at = Utc
+                    .timestamp_opt(commit.time().seconds(), 0)
+                    .single()
+                    .unwrap_or_else(Utc::now);
+                Ok(LogEntry {
+                    short_hash: oid.to_string()[..7].to_string(),
+                    message: commit.summary().ok().flatten().unwrap_or("(no message)").to_string(),
+                    committed_at,
+                })
+            })
+            .collect()
+    }
+
+    pub fn restore(vault_dir: &Path, commit: &str) -> Result<(), VaultError> {
+        let repo = open(vault_dir)?;
+        let commit = repo
+            .revparse_single(commit)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| VaultError::GitError(format!("Unknown commit '{}': {}", commit, e.message())))?;
+
+        let tree = commit.tree().map_err(git_err)?;
+        let entry = tree
+            .get_path(Path::new("vault.json"))
+            .map_err(|_| VaultError::GitError("That commit has no vault.json.".to_string()))?;
+        let blob = repo.find_blob(entry.id()).map_err(git_err)?;
+
+        std::fs::write(vault_dir.join("vault.json"), blob.content())?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "git"))]
+mod backend {
+    use super::LogEntry;
+    use crate::vault::VaultError;
+    use std::path::Path;
+
+    fn disabled() -> VaultError {
+        VaultError::GitError(
+            "Git-backed vault versioning requires the 'git' feature; rebuild with `--features git`.".to_string(),
+        )
+    }
+
+    pub fn init(_vault_dir: &Path) -> Result<(), VaultError> {
+        Err(disabled())
+    }
+
+    pub fn autocommit(_vault_dir: &Path, _message: &str) -> Result<(), VaultError> {
+        Ok(())
+    }
+
+    pub fn log(_vault_dir: &Path) -> Result<Vec<LogEntry>, VaultError> {
+        Err(disabled())
+    }
+
+    pub fn restore(_vault_dir: &Path, _commit: &str) -> Result<(), VaultError> {
+        Err(disabled())
+    }
+}
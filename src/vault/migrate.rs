@@ -0,0 +1,57 @@
+//! Vault schema version migrations
+//!
+//! Runs on every load so a `VaultFile` written by an older kookie gets
+//! upgraded to the schema this binary expects. Currently a no-op, since v1
+//! is the only schema that has ever shipped; future format changes add a
+//! step here instead of breaking old vaults outright.
+
+use super::{VaultError, VaultFile};
+
+/// The schema version this binary reads and writes.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Upgrades `file` to `CURRENT_VERSION`, running each version's migration
+/// in turn. Errors if `file` was written by a newer kookie than this binary
+/// understands - there's no way to safely downgrade a schema we don't know.
+pub fn upgrade(file: VaultFile) -> Result<VaultFile, VaultError> {
+    if file.version > CURRENT_VERSION {
+        return Err(VaultError::UnsupportedVersion(file.version));
+    }
+
+    // No migrations yet: v1 is the only schema that has ever shipped.
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kdf::KdfProfile;
+    use chrono::Utc;
+
+    fn file_with_version(version: u32) -> VaultFile {
+        VaultFile {
+            version,
+            salt: String::new(),
+            encrypted_data: String::new(),
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            kdf_profile: KdfProfile::default(),
+            hint: None,
+            compressed: false,
+            key_check: None,
+        }
+    }
+
+    #[test]
+    fn test_current_version_is_a_no_op() {
+        let file = file_with_version(CURRENT_VERSION);
+        let upgraded = upgrade(file).unwrap();
+        assert_eq!(upgraded.version, CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let file = file_with_version(CURRENT_VERSION + 1);
+        assert!(matches!(upgrade(file), Err(VaultError::UnsupportedVersion(_))));
+    }
+}
@@ -1,10 +1,13 @@
 //! Vault module for managing encrypted storage
 
+pub mod gitstore;
+pub mod migrate;
+pub mod schema;
 pub mod storage;
 pub mod types;
 
 use crate::crypto::{self, kdf};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -21,6 +24,8 @@ pub enum VaultError {
     WrongPassword,
     #[error("Secret not found: {0}")]
     SecretNotFound(String),
+    #[error("No trashed secret found: {0}")]
+    TrashedSecretNotFound(String),
     #[error("Duplicate secret name: {0}")]
     DuplicateName(String),
     #[error("IO error: {0}")]
@@ -31,6 +36,22 @@ pub enum VaultError {
     EncryptionError(String),
     #[error("Key derivation error: {0}")]
     KdfError(#[from] kdf::KdfError),
+    #[error("Invalid connection string: {0}")]
+    InvalidConnectionString(String),
+    #[error("Attachment too large: {0}")]
+    AttachmentTooLarge(String),
+    #[error("Attachment not found: {0}")]
+    AttachmentNotFound(String),
+    #[error("Corrupt attachment data: {0}")]
+    AttachmentDecodeError(String),
+    #[error("Vault created by a newer kookie (schema v{0}); please upgrade.")]
+    UnsupportedVersion(u32),
+    #[error("Invalid {0}: {1}")]
+    Invalid(String, String),
+    #[error("Vault data is malformed: {0}")]
+    Corrupted(String),
+    #[error("Git error: {0}")]
+    GitError(String),
 }
 
 /// Encrypted vault file format
@@ -41,6 +62,83 @@ pub struct VaultFile {
     pub encrypted_data: String,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
+    /// KDF cost profile used to derive the key, so unlocking re-derives it
+    /// with matching parameters. Defaults to `Standard` for vaults written
+    /// before this field existed.
+    #[serde(default)]
+    pub kdf_profile: kdf::KdfProfile,
+    /// Optional, user-supplied reminder of which master password was used.
+    /// Stored unencrypted by design (it has to be readable before the vault
+    /// is unlocked) - the `init` prompt warns against anything that would
+    /// reveal the actual password.
+    #[serde(default)]
+    pub hint: Option<String>,
+    /// Whether the plaintext `VaultData` was gzip-compressed before
+    /// encryption (see `COMPRESSION_THRESHOLD_BYTES`). Defaults to `false`
+    /// for vaults written before this field existed, which were never
+    /// compressed.
+    #[serde(default)]
+    pub compressed: bool,
+    /// HMAC verifier derived from the encryption key (see
+    /// `crypto::commitment`), checked before attempting to decrypt
+    /// `encrypted_data` so a wrong password is reported immediately rather
+    /// than via AES-GCM's authentication tag failing. `None` for vaults
+    /// written before this field existed; those fall back to the old
+    /// decrypt-and-see behavior.
+    #[serde(default)]
+    pub key_check: Option<String>,
+}
+
+/// Serialized `VaultData` larger than this is gzip-compressed before
+/// encryption; smaller vaults are left as plain JSON, since compression
+/// overhead isn't worth it below this size.
+const COMPRESSION_THRESHOLD_BYTES: usize = 16 * 1024;
+
+/// Gzip-compresses `data` at the default compression level.
+fn compress(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Gzip-decompresses `data`.
+fn decompress(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    let mut decoder = GzDecoder::new(data);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// Normalizes a secret name for `add_*` when `normalize_names` is enabled:
+/// lowercases it and collapses runs of whitespace/underscores into a single
+/// hyphen, so "GitHub", "github", and "git hub" all become "github"/"git-hub"
+/// and collide as duplicates instead of silently fragmenting the vault.
+pub(crate) fn normalize_secret_name(name: &str) -> String {
+    let lowered = name.trim().to_lowercase();
+    let mut normalized = String::with_capacity(lowered.len());
+    let mut last_was_hyphen = false;
+
+    for ch in lowered.chars() {
+        let mapped = if ch.is_whitespace() || ch == '_' { '-' } else { ch };
+        if mapped == '-' {
+            if last_was_hyphen {
+                continue;
+            }
+            last_was_hyphen = true;
+        } else {
+            last_was_hyphen = false;
+        }
+        normalized.push(mapped);
+    }
+
+    normalized
 }
 
 /// Decrypted vault contents
@@ -51,6 +149,305 @@ pub struct VaultData {
     pub notes: Vec<Note>,
     pub db_credentials: Vec<DbCredential>,
     pub tokens: Vec<Token>,
+    #[serde(default)]
+    pub custom_secrets: Vec<Custom>,
+    #[serde(default)]
+    pub ssh_keys: Vec<SshKey>,
+    /// Secrets removed via `Vault::trash`, kept until `Vault::restore` or
+    /// `Vault::empty_trash`/`purge_expired_trash` removes them for good.
+    #[serde(default)]
+    pub trash: Vec<TrashEntry>,
+}
+
+/// A type-erased view of a secret, just enough to sort, filter and list it -
+/// shared by commands (`recent`, `changelog`) that need a unified view
+/// across all seven secret-type vectors without printing secret values.
+pub struct SecretEntry {
+    pub secret_type: SecretType,
+    pub id: String,
+    pub name: String,
+    pub extra: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub favorite: bool,
+}
+
+/// Result of resolving a user-supplied id/name/prefix against the vault, for
+/// commands (`get`, `delete`) that want git-short-hash-style id prefixes in
+/// addition to full ids and exact names.
+pub enum PrefixResult {
+    /// Resolved to exactly one secret.
+    Unique(SecretEntry),
+    /// The prefix matched more than one secret; these are the candidates.
+    Ambiguous(Vec<SecretEntry>),
+    /// Nothing matched.
+    None,
+}
+
+impl VaultData {
+    /// Collects a type-erased view of every secret across all seven vectors.
+    pub fn all_entries(&self) -> Vec<SecretEntry> {
+        let mut entries = Vec::new();
+
+        for p in &self.passwords {
+            entries.push(SecretEntry {
+                secret_type: SecretType::Password,
+                id: p.id.clone(),
+                name: p.name.clone(),
+                extra: p.username.clone(),
+                created_at: p.created_at,
+                updated_at: p.updated_at,
+                favorite: p.favorite,
+            });
+        }
+
+        for k in &self.api_keys {
+            entries.push(SecretEntry {
+                secret_type: SecretType::ApiKey,
+                id: k.id.clone(),
+                name: k.name.clone(),
+                extra: k.service.clone(),
+                created_at: k.created_at,
+                updated_at: k.updated_at,
+                favorite: k.favorite,
+            });
+        }
+
+        for n in &self.notes {
+            entries.push(SecretEntry {
+                secret_type: SecretType::Note,
+                id: n.id.clone(),
+                name: n.name.clone(),
+                extra: None,
+                created_at: n.created_at,
+                updated_at: n.updated_at,
+                favorite: n.favorite,
+            });
+        }
+
+        for c in &self.db_credentials {
+            entries.push(SecretEntry {
+                secret_type: SecretType::DbCredential,
+                id: c.id.clone(),
+                name: c.name.clone(),
+                extra: Some(format!("{}@{}", c.username, c.host)),
+                created_at: c.created_at,
+                updated_at: c.updated_at,
+                favorite: c.favorite,
+            });
+        }
+
+        for t in &self.tokens {
+            entries.push(SecretEntry {
+                secret_type: SecretType::Token,
+                id: t.id.clone(),
+                name: t.name.clone(),
+                extra: None,
+                created_at: t.created_at,
+                updated_at: t.updated_at,
+                favorite: t.favorite,
+            });
+        }
+
+        for c in &self.custom_secrets {
+            entries.push(SecretEntry {
+                secret_type: SecretType::Custom,
+                id: c.id.clone(),
+                name: c.name.clone(),
+                extra: Some(format!("{} fields", c.fields.len())),
+                created_at: c.created_at,
+                updated_at: c.updated_at,
+                favorite: c.favorite,
+            });
+        }
+
+        for s in &self.ssh_keys {
+            entries.push(SecretEntry {
+                secret_type: SecretType::SshKey,
+                id: s.id.clone(),
+                name: s.name.clone(),
+                extra: Some(s.key_type.clone()),
+                created_at: s.created_at,
+                updated_at: s.updated_at,
+                favorite: s.favorite,
+            });
+        }
+
+        entries
+    }
+
+    /// Finds the attachments list for whichever secret type `id_or_name`
+    /// belongs to, so `Vault::attach` doesn't need to know the secret's type.
+    fn attachments_mut(&mut self, id_or_name: &str) -> Option<&mut Vec<Attachment>> {
+        if let Some(p) = self.passwords.iter_mut().find(|p| p.id == id_or_name || p.name == id_or_name) {
+            return Some(&mut p.attachments);
+        }
+        if let Some(k) = self.api_keys.iter_mut().find(|k| k.id == id_or_name || k.name == id_or_name) {
+            return Some(&mut k.attachments);
+        }
+        if let Some(n) = self.notes.iter_mut().find(|n| n.id == id_or_name || n.name == id_or_name) {
+            return Some(&mut n.attachments);
+        }
+        if let Some(c) = self.db_credentials.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.attachments);
+        }
+        if let Some(t) = self.tokens.iter_mut().find(|t| t.id == id_or_name || t.name == id_or_name) {
+            return Some(&mut t.attachments);
+        }
+        if let Some(c) = self.custom_secrets.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.attachments);
+        }
+        if let Some(s) = self.ssh_keys.iter_mut().find(|s| s.id == id_or_name || s.name == id_or_name) {
+            return Some(&mut s.attachments);
+        }
+        None
+    }
+
+    /// Finds the `favorite` flag for whichever secret type `id_or_name`
+    /// belongs to, so `Vault::set_favorite` doesn't need to know the type.
+    fn favorite_mut(&mut self, id_or_name: &str) -> Option<&mut bool> {
+        if let Some(p) = self.passwords.iter_mut().find(|p| p.id == id_or_name || p.name == id_or_name) {
+            return Some(&mut p.favorite);
+        }
+        if let Some(k) = self.api_keys.iter_mut().find(|k| k.id == id_or_name || k.name == id_or_name) {
+            return Some(&mut k.favorite);
+        }
+        if let Some(n) = self.notes.iter_mut().find(|n| n.id == id_or_name || n.name == id_or_name) {
+            return Some(&mut n.favorite);
+        }
+        if let Some(c) = self.db_credentials.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.favorite);
+        }
+        if let Some(t) = self.tokens.iter_mut().find(|t| t.id == id_or_name || t.name == id_or_name) {
+            return Some(&mut t.favorite);
+        }
+        if let Some(c) = self.custom_secrets.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.favorite);
+        }
+        if let Some(s) = self.ssh_keys.iter_mut().find(|s| s.id == id_or_name || s.name == id_or_name) {
+            return Some(&mut s.favorite);
+        }
+        None
+    }
+
+    /// Finds the `burn_after_read` flag for whichever secret type
+    /// `id_or_name` belongs to, so `Vault::set_burn_after_read` doesn't need
+    /// to know the type.
+    fn burn_after_read_mut(&mut self, id_or_name: &str) -> Option<&mut bool> {
+        if let Some(p) = self.passwords.iter_mut().find(|p| p.id == id_or_name || p.name == id_or_name) {
+            return Some(&mut p.burn_after_read);
+        }
+        if let Some(k) = self.api_keys.iter_mut().find(|k| k.id == id_or_name || k.name == id_or_name) {
+            return Some(&mut k.burn_after_read);
+        }
+        if let Some(n) = self.notes.iter_mut().find(|n| n.id == id_or_name || n.name == id_or_name) {
+            return Some(&mut n.burn_after_read);
+        }
+        if let Some(c) = self.db_credentials.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.burn_after_read);
+        }
+        if let Some(t) = self.tokens.iter_mut().find(|t| t.id == id_or_name || t.name == id_or_name) {
+            return Some(&mut t.burn_after_read);
+        }
+        if let Some(c) = self.custom_secrets.iter_mut().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&mut c.burn_after_read);
+        }
+        if let Some(s) = self.ssh_keys.iter_mut().find(|s| s.id == id_or_name || s.name == id_or_name) {
+            return Some(&mut s.burn_after_read);
+        }
+        None
+    }
+
+    /// Finds a secret's attachments list by id or name, read-only.
+    fn attachments(&self, id_or_name: &str) -> Option<&Vec<Attachment>> {
+        if let Some(p) = self.passwords.iter().find(|p| p.id == id_or_name || p.name == id_or_name) {
+            return Some(&p.attachments);
+        }
+        if let Some(k) = self.api_keys.iter().find(|k| k.id == id_or_name || k.name == id_or_name) {
+            return Some(&k.attachments);
+        }
+        if let Some(n) = self.notes.iter().find(|n| n.id == id_or_name || n.name == id_or_name) {
+            return Some(&n.attachments);
+        }
+        if let Some(c) = self.db_credentials.iter().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&c.attachments);
+        }
+        if let Some(t) = self.tokens.iter().find(|t| t.id == id_or_name || t.name == id_or_name) {
+            return Some(&t.attachments);
+        }
+        if let Some(c) = self.custom_secrets.iter().find(|c| c.id == id_or_name || c.name == id_or_name) {
+            return Some(&c.attachments);
+        }
+        if let Some(s) = self.ssh_keys.iter().find(|s| s.id == id_or_name || s.name == id_or_name) {
+            return Some(&s.attachments);
+        }
+        None
+    }
+
+    /// Iterates every secret across all seven vectors as a type-erased
+    /// `Secret`, for `Vault::iter_secrets` and `StorageBackend::PerSecretFile`.
+    fn secrets(&self) -> impl Iterator<Item = Secret> + '_ {
+        self.passwords.iter().cloned().map(Secret::from)
+            .chain(self.api_keys.iter().cloned().map(Secret::from))
+            .chain(self.notes.iter().cloned().map(Secret::from))
+            .chain(self.db_credentials.iter().cloned().map(Secret::from))
+            .chain(self.tokens.iter().cloned().map(Secret::from))
+            .chain(self.custom_secrets.iter().cloned().map(Secret::from))
+            .chain(self.ssh_keys.iter().cloned().map(Secret::from))
+    }
+
+    /// Empties all seven secret-type vectors, leaving `trash` untouched.
+    /// Used by `StorageBackend::PerSecretFile` saves, where the vectors live
+    /// in individual `secrets/<id>.enc` files instead of `vault.json`.
+    fn clear_secrets(&mut self) {
+        self.passwords.clear();
+        self.api_keys.clear();
+        self.notes.clear();
+        self.db_credentials.clear();
+        self.tokens.clear();
+        self.custom_secrets.clear();
+        self.ssh_keys.clear();
+    }
+
+    /// Appends a type-erased `Secret` back into its matching vector. Used by
+    /// `StorageBackend::PerSecretFile` reads, to rebuild the seven vectors
+    /// from individual secret files.
+    fn insert_secret(&mut self, secret: Secret) {
+        match secret {
+            Secret::Password(s) => self.passwords.push(s),
+            Secret::ApiKey(s) => self.api_keys.push(s),
+            Secret::Note(s) => self.notes.push(s),
+            Secret::DbCredential(s) => self.db_credentials.push(s),
+            Secret::Token(s) => self.tokens.push(s),
+            Secret::Custom(s) => self.custom_secrets.push(s),
+            Secret::SshKey(s) => self.ssh_keys.push(s),
+        }
+    }
+}
+
+/// Summarizes a `mutate` call for `Vault::autocommit`'s commit message, by
+/// diffing the entry lists from before and after the mutation ran. Handles
+/// the common single-secret cases (`add`/`delete`); anything messier
+/// (batch edits via `with_session`, trash purges, ...) falls back to a
+/// generic message rather than guessing.
+fn describe_change(before: &[SecretEntry], after: &[SecretEntry]) -> String {
+    let added: Vec<&SecretEntry> = after.iter().filter(|a| !before.iter().any(|b| b.id == a.id)).collect();
+    let removed: Vec<&SecretEntry> = before.iter().filter(|b| !after.iter().any(|a| a.id == b.id)).collect();
+
+    match (added.as_slice(), removed.as_slice()) {
+        ([added], []) => format!("add {} {}", added.secret_type, added.name),
+        ([], [removed]) => format!("delete {} {}", removed.secret_type, removed.name),
+        ([], []) => {
+            let changed = after.iter().find(|a| {
+                before.iter().any(|b| b.id == a.id && b.updated_at != a.updated_at)
+            });
+            match changed {
+                Some(e) => format!("update {} {}", e.secret_type, e.name),
+                None => "update vault".to_string(),
+            }
+        }
+        _ => "update vault".to_string(),
+    }
 }
 
 /// Main vault structure
@@ -59,6 +456,10 @@ pub struct Vault {
     pub data: VaultData,
     key: Option<[u8; 32]>,
     salt: String,
+    kdf_profile: kdf::KdfProfile,
+    hint: Option<String>,
+    storage_backend: storage::StorageBackend,
+    normalize_names: bool,
 }
 
 impl Vault {
@@ -69,23 +470,77 @@ impl Vault {
             data: VaultData::default(),
             key: None,
             salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+            normalize_names: false,
         }
     }
 
+    /// Selects which on-disk layout `save`/`unlock` use for the seven
+    /// secret-type vectors. Callers read the configured backend from
+    /// `SessionConfig::storage_backend` (the `commands` layer already
+    /// depends on both `vault` and `session::cache`, so this keeps
+    /// `vault` itself from having to depend on `session::cache`) and set
+    /// it here before unlocking/initializing.
+    pub fn set_storage_backend(&mut self, backend: storage::StorageBackend) {
+        self.storage_backend = backend;
+    }
+
+    /// Whether `add_*` should normalize incoming names (see
+    /// `normalize_secret_name`) before storing and checking for
+    /// collisions. Read from `SessionConfig::normalize_names` and set here
+    /// by callers for the same reason `set_storage_backend` exists - so
+    /// `vault` doesn't have to depend on `session::cache` itself.
+    pub fn set_normalize_names(&mut self, enabled: bool) {
+        self.normalize_names = enabled;
+    }
+
+    /// Whether `add_*` normalizes names on this vault, set via
+    /// `set_normalize_names`. Lets callers (e.g. `commands::add`) predict
+    /// the name a secret will actually be stored under before calling
+    /// `add_*`, since it consumes the value by move.
+    pub fn normalizes_names(&self) -> bool {
+        self.normalize_names
+    }
+
+    /// The master-password hint, if one was set during `init`
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    /// Sets the master-password hint, persisted on the next `save()`
+    pub fn set_hint(&mut self, hint: Option<String>) {
+        self.hint = hint;
+    }
+
     /// Checks if vault exists
     pub fn exists(&self) -> bool {
         self.path.exists()
     }
 
     /// Initializes a new vault with the given master password
+    #[allow(dead_code)]
     pub fn init(&mut self, master_password: &str) -> Result<(), VaultError> {
+        self.init_with_profile(master_password, kdf::KdfProfile::Standard)
+    }
+
+    /// Initializes a new vault with the given master password and KDF profile
+    pub fn init_with_profile(
+        &mut self,
+        master_password: &str,
+        profile: kdf::KdfProfile,
+    ) -> Result<(), VaultError> {
         if self.exists() {
             return Err(VaultError::AlreadyExists);
         }
 
         // Generate salt and derive key
         self.salt = kdf::generate_salt();
-        self.key = Some(kdf::derive_key(master_password, &self.salt)?);
+        self.kdf_profile = profile;
+        self.key = Some(crate::utils::display::with_spinner("Deriving key...", || {
+            kdf::derive_key_with_profile(master_password, &self.salt, profile)
+        })?);
         self.data = VaultData::default();
 
         // Save the vault
@@ -95,10 +550,23 @@ impl Vault {
     }
 
     /// Initializes a new vault, forcing overwrite if exists
+    #[allow(dead_code)]
     pub fn init_force(&mut self, master_password: &str) -> Result<(), VaultError> {
+        self.init_force_with_profile(master_password, kdf::KdfProfile::Standard)
+    }
+
+    /// Initializes a new vault, forcing overwrite if exists, with the given KDF profile
+    pub fn init_force_with_profile(
+        &mut self,
+        master_password: &str,
+        profile: kdf::KdfProfile,
+    ) -> Result<(), VaultError> {
         // Generate salt and derive key
         self.salt = kdf::generate_salt();
-        self.key = Some(kdf::derive_key(master_password, &self.salt)?);
+        self.kdf_profile = profile;
+        self.key = Some(crate::utils::display::with_spinner("Deriving key...", || {
+            kdf::derive_key_with_profile(master_password, &self.salt, profile)
+        })?);
         self.data = VaultData::default();
 
         // Save the vault
@@ -107,27 +575,85 @@ impl Vault {
         Ok(())
     }
 
+    /// Opens a vault at `path` with the given master password, without any
+    /// terminal interaction - derives the key and decrypts in one step.
+    /// Intended for embedding kookie as a library dependency; the CLI's
+    /// `ensure_unlocked` builds on top of this after prompting for the
+    /// password interactively.
+    #[allow(dead_code)]
+    pub fn open(path: impl Into<PathBuf>, master_password: &str) -> Result<Self, VaultError> {
+        let mut vault = Self {
+            path: path.into(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+            normalize_names: false,
+        };
+        vault.unlock(master_password)?;
+        Ok(vault)
+    }
+
     /// Unlocks the vault with the master password
     pub fn unlock(&mut self, master_password: &str) -> Result<(), VaultError> {
         if !self.exists() {
             return Err(VaultError::NotInitialized);
         }
 
-        // Load vault file
-        let vault_file = storage::load_vault_file(&self.path)?;
+        // Load vault file, upgrading it to the current schema if needed
+        let on_disk = storage::load_vault_file(&self.path)?;
+        let needs_rewrite = on_disk.version != migrate::CURRENT_VERSION;
+        let vault_file = migrate::upgrade(on_disk)?;
         self.salt = vault_file.salt.clone();
+        self.kdf_profile = vault_file.kdf_profile;
+        // Unencrypted, so available even if the password below turns out wrong.
+        self.hint = vault_file.hint.clone();
 
         // Derive key
-        let key = kdf::derive_key(master_password, &vault_file.salt)?;
+        let key = crate::utils::display::with_spinner("Deriving key...", || {
+            kdf::derive_key_with_profile(master_password, &vault_file.salt, vault_file.kdf_profile)
+        })?;
 
-        // Try to decrypt
-        let decrypted = crypto::decrypt(&key, &vault_file.encrypted_data)
-            .map_err(|_| VaultError::WrongPassword)?;
+        // Check the key-commitment verifier first, if the vault has one, so
+        // a wrong password is reported immediately instead of relying on
+        // AES-GCM's authentication tag to carry that meaning too. Vaults
+        // written before this field existed (`key_check: None`) fall
+        // through to the decrypt-and-see behavior below.
+        if let Some(key_check) = &vault_file.key_check {
+            if !crypto::commitment::verify(&key, key_check) {
+                return Err(VaultError::WrongPassword);
+            }
+        }
+
+        // Try to decrypt. A malformed/truncated ciphertext (InvalidFormat)
+        // means the vault file itself is corrupted - the password can't be
+        // at fault there, unlike a failed authentication tag check, which
+        // is consistent with either a wrong password or tampered data.
+        let decrypted = crypto::decrypt(&key, &vault_file.encrypted_data).map_err(|e| match e {
+            crypto::cipher::CipherError::InvalidFormat => {
+                VaultError::Corrupted("encrypted_data is truncated or not valid base64".to_string())
+            }
+            _ => VaultError::WrongPassword,
+        })?;
+        let decrypted = if vault_file.compressed { decompress(&decrypted)? } else { decrypted };
 
         // Deserialize
-        self.data = serde_json::from_slice(&decrypted)?;
+        let mut data: VaultData = serde_json::from_slice(&decrypted)?;
         self.key = Some(key);
 
+        // Under `StorageBackend::PerSecretFile`, the seven secret vectors
+        // above are empty (they live in `secrets/<id>.enc` instead) - load
+        // them back in, the same way `read_data` does for `mutate`.
+        self.load_per_secret_files(&mut data)?;
+        self.data = data;
+
+        // Persist the upgraded schema once, so future loads skip re-migrating.
+        if needs_rewrite {
+            self.save()?;
+        }
+
         Ok(())
     }
 
@@ -137,6 +663,15 @@ impl Vault {
         self.key.is_some()
     }
 
+    /// Returns the cached encryption key, if the vault is unlocked.
+    ///
+    /// Lets callers that already hold an unlocked `Vault` (e.g. to save a
+    /// session) reuse the key that was derived during `unlock()`, instead
+    /// of re-deriving it from the password and paying the KDF cost again.
+    pub fn key(&self) -> Option<[u8; 32]> {
+        self.key
+    }
+
     /// Locks the vault (clears key from memory)
     #[allow(dead_code)]
     pub fn lock(&mut self) {
@@ -145,38 +680,191 @@ impl Vault {
 
     /// Saves the vault to disk
     pub fn save(&self) -> Result<(), VaultError> {
+        let _lock = storage::VaultLock::acquire()?;
+        self.save_data(&self.data)?;
+        self.autocommit("update vault");
+        Ok(())
+    }
+
+    /// Auto-commits the vault directory with `message` if `git_autocommit`
+    /// is set in config. A no-op if it isn't, or if `kookie git init` was
+    /// never run - see `gitstore::autocommit`.
+    fn autocommit(&self, message: &str) {
+        if !crate::session::cache::load_config().git_autocommit {
+            return;
+        }
+        if let Some(vault_dir) = self.path.parent() {
+            gitstore::autocommit(vault_dir, message);
+        }
+    }
+
+    /// Serializes, encrypts, and writes the given data to disk, without
+    /// touching `self.data`. Callers are expected to hold `VaultLock`.
+    fn save_data(&self, data: &VaultData) -> Result<(), VaultError> {
         let key = self.key.ok_or(VaultError::WrongPassword)?;
 
-        // Serialize data
-        let data_json = serde_json::to_vec(&self.data)?;
+        // Under `StorageBackend::PerSecretFile`, the seven secret-type
+        // vectors are written out as individual files instead, so the blob
+        // encrypted below only needs to carry `trash` plus the (now empty)
+        // vectors.
+        let mut data = data.clone();
+        if self.storage_backend == storage::StorageBackend::PerSecretFile {
+            self.save_secrets_per_file(&data)?;
+            data.clear_secrets();
+        }
+
+        let data_json = serde_json::to_vec(&data)?;
+
+        let compressed = data_json.len() > COMPRESSION_THRESHOLD_BYTES;
+        let payload = if compressed { compress(&data_json)? } else { data_json };
 
-        // Encrypt
-        let encrypted = crypto::encrypt(&key, &data_json)
+        let encrypted = crypto::encrypt(&key, &payload)
             .map_err(|e| VaultError::EncryptionError(e.to_string()))?;
 
-        // Create vault file
         let vault_file = VaultFile {
-            version: 1,
+            version: migrate::CURRENT_VERSION,
             salt: self.salt.clone(),
             encrypted_data: encrypted,
             created_at: Utc::now(),
             modified_at: Utc::now(),
+            kdf_profile: self.kdf_profile,
+            hint: self.hint.clone(),
+            compressed,
+            key_check: Some(crypto::commitment::compute(&key)),
         };
 
-        // Save
-        storage::save_vault_file(&self.path, &vault_file)?;
+        storage::save_vault_file(&self.path, &vault_file)
+    }
+
+    /// Writes every secret in `data` out to its own encrypted file under
+    /// `secrets/<id>.enc`, and refreshes the unencrypted index alongside
+    /// them. Deletes files for ids that no longer exist in `data`, so a
+    /// `kookie delete` is reflected by removing a file (the point of this
+    /// backend: a git diff touches only what actually changed).
+    fn save_secrets_per_file(&self, data: &VaultData) -> Result<(), VaultError> {
+        let key = self.key.ok_or(VaultError::WrongPassword)?;
+
+        let previous = storage::load_secret_index()?;
+        let mut index = storage::SecretIndex::default();
+
+        for secret in data.secrets() {
+            let secret_json = serde_json::to_vec(&secret)?;
+            let encrypted = crypto::encrypt(&key, &secret_json)
+                .map_err(|e| VaultError::EncryptionError(e.to_string()))?;
+            storage::save_secret_file(secret.id(), &encrypted)?;
+            index.entries.push(storage::SecretIndexEntry {
+                id: secret.id().to_string(),
+                secret_type: secret.secret_type(),
+            });
+        }
+
+        let current_ids: std::collections::HashSet<&str> =
+            index.entries.iter().map(|e| e.id.as_str()).collect();
+        for stale in previous.entries.iter().filter(|e| !current_ids.contains(e.id.as_str())) {
+            storage::delete_secret_file(&stale.id)?;
+        }
+
+        storage::save_secret_index(&index)
+    }
+
+    /// Decrypts and returns the data currently on disk, using this vault's key.
+    fn read_data(&self) -> Result<VaultData, VaultError> {
+        let key = self.key.ok_or(VaultError::WrongPassword)?;
+        let vault_file = migrate::upgrade(storage::load_vault_file(&self.path)?)?;
+        let decrypted = crypto::decrypt(&key, &vault_file.encrypted_data)
+            .map_err(|_| VaultError::WrongPassword)?;
+        let decrypted = if vault_file.compressed { decompress(&decrypted)? } else { decrypted };
+        let mut data: VaultData = serde_json::from_slice(&decrypted)?;
+        self.load_per_secret_files(&mut data)?;
+        Ok(data)
+    }
 
+    /// Under `StorageBackend::PerSecretFile`, reads every secret out of its
+    /// own `secrets/<id>.enc` file (per `storage::load_secret_index`) and
+    /// appends it to `data`'s seven vectors, which `save_data` leaves empty
+    /// on disk under that backend. A no-op under `StorageBackend::Monolithic`.
+    /// Shared by `read_data` (used by `mutate`) and `unlock`, so every read
+    /// path reconstructs the same data `mutate`-based writes produce.
+    fn load_per_secret_files(&self, data: &mut VaultData) -> Result<(), VaultError> {
+        if self.storage_backend != storage::StorageBackend::PerSecretFile {
+            return Ok(());
+        }
+        let key = self.key.ok_or(VaultError::WrongPassword)?;
+        for entry in storage::load_secret_index()?.entries {
+            let encrypted = storage::load_secret_file(&entry.id)?;
+            let decrypted = crypto::decrypt(&key, &encrypted).map_err(|_| VaultError::WrongPassword)?;
+            data.insert_secret(serde_json::from_slice(&decrypted)?);
+        }
         Ok(())
     }
 
+    /// Applies a mutation under an exclusive file lock, reloading the
+    /// latest on-disk data first so a concurrent writer's changes aren't
+    /// lost (last-write-wins without this would silently drop them).
+    fn mutate<T, F>(&mut self, f: F) -> Result<T, VaultError>
+    where
+        F: FnOnce(&mut VaultData) -> Result<T, VaultError>,
+    {
+        let _lock = storage::VaultLock::acquire()?;
+        let mut data = if self.exists() { self.read_data()? } else { self.data.clone() };
+        let before = data.all_entries();
+        let result = f(&mut data)?;
+        self.save_data(&data)?;
+        self.autocommit(&describe_change(&before, &data.all_entries()));
+        self.data = data;
+        Ok(result)
+    }
+
+    /// Runs several operations against the vault's decrypted data under a
+    /// single file lock, saving once when `f` returns instead of once per
+    /// operation.
+    ///
+    /// Intended for batch commands that would otherwise call `add_*`/
+    /// `delete_*` in a loop, each of which locks, reads, and saves on its
+    /// own - fine for a single change, wasteful for many. The vault must
+    /// already be unlocked (e.g. via `ensure_unlocked`); this does not
+    /// derive a key, it just reuses the one already cached on `self`.
+    #[allow(dead_code)]
+    pub fn with_session<T, F>(&mut self, f: F) -> Result<T, VaultError>
+    where
+        F: FnOnce(&mut VaultData) -> Result<T, VaultError>,
+    {
+        self.mutate(f)
+    }
+
     // === Password Operations ===
 
-    pub fn add_password(&mut self, password: Password) -> Result<(), VaultError> {
-        if self.data.passwords.iter().any(|p| p.name == password.name) {
-            return Err(VaultError::DuplicateName(password.name));
+    pub fn add_password(&mut self, mut password: Password) -> Result<(), VaultError> {
+        if self.normalize_names {
+            password.name = normalize_secret_name(&password.name);
+        }
+        self.mutate(|data| {
+            if data.passwords.iter().any(|p| p.name == password.name) {
+                return Err(VaultError::DuplicateName(password.name.clone()));
+            }
+            data.passwords.push(password);
+            Ok(())
+        })
+    }
+
+    /// Resolves `id_or_name` to a single secret across all seven secret
+    /// types, accepting a unique id prefix (like a short git hash) in
+    /// addition to a full id or exact name. An exact name match always wins
+    /// over a prefix match, even if the prefix also matches other secrets -
+    /// so a secret named e.g. "ab" isn't shadowed by ids starting with "ab".
+    pub fn resolve_prefix(&self, id_or_name: &str) -> PrefixResult {
+        let mut entries = self.data.all_entries();
+
+        if let Some(pos) = entries.iter().position(|e| e.name == id_or_name) {
+            return PrefixResult::Unique(entries.swap_remove(pos));
+        }
+
+        entries.retain(|e| e.id.starts_with(id_or_name));
+        match entries.len() {
+            0 => PrefixResult::None,
+            1 => PrefixResult::Unique(entries.remove(0)),
+            _ => PrefixResult::Ambiguous(entries),
         }
-        self.data.passwords.push(password);
-        self.save()
     }
 
     pub fn get_password(&self, id_or_name: &str) -> Option<&Password> {
@@ -184,22 +872,27 @@ impl Vault {
     }
 
     pub fn delete_password(&mut self, id_or_name: &str) -> Result<Password, VaultError> {
-        let idx = self.data.passwords.iter()
-            .position(|p| p.id == id_or_name || p.name == id_or_name)
-            .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
-        let removed = self.data.passwords.remove(idx);
-        self.save()?;
-        Ok(removed)
+        self.mutate(|data| {
+            let idx = data.passwords.iter()
+                .position(|p| p.id == id_or_name || p.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.passwords.remove(idx))
+        })
     }
 
     // === API Key Operations ===
 
-    pub fn add_api_key(&mut self, api_key: ApiKey) -> Result<(), VaultError> {
-        if self.data.api_keys.iter().any(|k| k.name == api_key.name) {
-            return Err(VaultError::DuplicateName(api_key.name));
+    pub fn add_api_key(&mut self, mut api_key: ApiKey) -> Result<(), VaultError> {
+        if self.normalize_names {
+            api_key.name = normalize_secret_name(&api_key.name);
         }
-        self.data.api_keys.push(api_key);
-        self.save()
+        self.mutate(|data| {
+            if data.api_keys.iter().any(|k| k.name == api_key.name) {
+                return Err(VaultError::DuplicateName(api_key.name.clone()));
+            }
+            data.api_keys.push(api_key);
+            Ok(())
+        })
     }
 
     pub fn get_api_key(&self, id_or_name: &str) -> Option<&ApiKey> {
@@ -207,22 +900,27 @@ impl Vault {
     }
 
     pub fn delete_api_key(&mut self, id_or_name: &str) -> Result<ApiKey, VaultError> {
-        let idx = self.data.api_keys.iter()
-            .position(|k| k.id == id_or_name || k.name == id_or_name)
-            .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
-        let removed = self.data.api_keys.remove(idx);
-        self.save()?;
-        Ok(removed)
+        self.mutate(|data| {
+            let idx = data.api_keys.iter()
+                .position(|k| k.id == id_or_name || k.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.api_keys.remove(idx))
+        })
     }
 
     // === Note Operations ===
 
-    pub fn add_note(&mut self, note: Note) -> Result<(), VaultError> {
-        if self.data.notes.iter().any(|n| n.name == note.name) {
-            return Err(VaultError::DuplicateName(note.name));
+    pub fn add_note(&mut self, mut note: Note) -> Result<(), VaultError> {
+        if self.normalize_names {
+            note.name = normalize_secret_name(&note.name);
         }
-        self.data.notes.push(note);
-        self.save()
+        self.mutate(|data| {
+            if data.notes.iter().any(|n| n.name == note.name) {
+                return Err(VaultError::DuplicateName(note.name.clone()));
+            }
+            data.notes.push(note);
+            Ok(())
+        })
     }
 
     pub fn get_note(&self, id_or_name: &str) -> Option<&Note> {
@@ -230,22 +928,27 @@ impl Vault {
     }
 
     pub fn delete_note(&mut self, id_or_name: &str) -> Result<Note, VaultError> {
-        let idx = self.data.notes.iter()
-            .position(|n| n.id == id_or_name || n.name == id_or_name)
-            .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
-        let removed = self.data.notes.remove(idx);
-        self.save()?;
-        Ok(removed)
+        self.mutate(|data| {
+            let idx = data.notes.iter()
+                .position(|n| n.id == id_or_name || n.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.notes.remove(idx))
+        })
     }
 
     // === DB Credential Operations ===
 
-    pub fn add_db_credential(&mut self, cred: DbCredential) -> Result<(), VaultError> {
-        if self.data.db_credentials.iter().any(|c| c.name == cred.name) {
-            return Err(VaultError::DuplicateName(cred.name));
+    pub fn add_db_credential(&mut self, mut cred: DbCredential) -> Result<(), VaultError> {
+        if self.normalize_names {
+            cred.name = normalize_secret_name(&cred.name);
         }
-        self.data.db_credentials.push(cred);
-        self.save()
+        self.mutate(|data| {
+            if data.db_credentials.iter().any(|c| c.name == cred.name) {
+                return Err(VaultError::DuplicateName(cred.name.clone()));
+            }
+            data.db_credentials.push(cred);
+            Ok(())
+        })
     }
 
     pub fn get_db_credential(&self, id_or_name: &str) -> Option<&DbCredential> {
@@ -253,22 +956,27 @@ impl Vault {
     }
 
     pub fn delete_db_credential(&mut self, id_or_name: &str) -> Result<DbCredential, VaultError> {
-        let idx = self.data.db_credentials.iter()
-            .position(|c| c.id == id_or_name || c.name == id_or_name)
-            .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
-        let removed = self.data.db_credentials.remove(idx);
-        self.save()?;
-        Ok(removed)
+        self.mutate(|data| {
+            let idx = data.db_credentials.iter()
+                .position(|c| c.id == id_or_name || c.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.db_credentials.remove(idx))
+        })
     }
 
     // === Token Operations ===
 
-    pub fn add_token(&mut self, token: Token) -> Result<(), VaultError> {
-        if self.data.tokens.iter().any(|t| t.name == token.name) {
-            return Err(VaultError::DuplicateName(token.name));
+    pub fn add_token(&mut self, mut token: Token) -> Result<(), VaultError> {
+        if self.normalize_names {
+            token.name = normalize_secret_name(&token.name);
         }
-        self.data.tokens.push(token);
-        self.save()
+        self.mutate(|data| {
+            if data.tokens.iter().any(|t| t.name == token.name) {
+                return Err(VaultError::DuplicateName(token.name.clone()));
+            }
+            data.tokens.push(token);
+            Ok(())
+        })
     }
 
     pub fn get_token(&self, id_or_name: &str) -> Option<&Token> {
@@ -276,12 +984,299 @@ impl Vault {
     }
 
     pub fn delete_token(&mut self, id_or_name: &str) -> Result<Token, VaultError> {
-        let idx = self.data.tokens.iter()
-            .position(|t| t.id == id_or_name || t.name == id_or_name)
+        self.mutate(|data| {
+            let idx = data.tokens.iter()
+                .position(|t| t.id == id_or_name || t.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.tokens.remove(idx))
+        })
+    }
+
+    // === Custom Secret Operations ===
+
+    pub fn add_custom(&mut self, mut custom: Custom) -> Result<(), VaultError> {
+        if self.normalize_names {
+            custom.name = normalize_secret_name(&custom.name);
+        }
+        self.mutate(|data| {
+            if data.custom_secrets.iter().any(|c| c.name == custom.name) {
+                return Err(VaultError::DuplicateName(custom.name.clone()));
+            }
+            data.custom_secrets.push(custom);
+            Ok(())
+        })
+    }
+
+    pub fn get_custom(&self, id_or_name: &str) -> Option<&Custom> {
+        self.data.custom_secrets.iter().find(|c| c.id == id_or_name || c.name == id_or_name)
+    }
+
+    pub fn delete_custom(&mut self, id_or_name: &str) -> Result<Custom, VaultError> {
+        self.mutate(|data| {
+            let idx = data.custom_secrets.iter()
+                .position(|c| c.id == id_or_name || c.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.custom_secrets.remove(idx))
+        })
+    }
+
+    // === SSH Key Operations ===
+
+    pub fn add_ssh_key(&mut self, mut ssh_key: SshKey) -> Result<(), VaultError> {
+        if self.normalize_names {
+            ssh_key.name = normalize_secret_name(&ssh_key.name);
+        }
+        self.mutate(|data| {
+            if data.ssh_keys.iter().any(|s| s.name == ssh_key.name) {
+                return Err(VaultError::DuplicateName(ssh_key.name.clone()));
+            }
+            data.ssh_keys.push(ssh_key);
+            Ok(())
+        })
+    }
+
+    pub fn get_ssh_key(&self, id_or_name: &str) -> Option<&SshKey> {
+        self.data.ssh_keys.iter().find(|s| s.id == id_or_name || s.name == id_or_name)
+    }
+
+    pub fn delete_ssh_key(&mut self, id_or_name: &str) -> Result<SshKey, VaultError> {
+        self.mutate(|data| {
+            let idx = data.ssh_keys.iter()
+                .position(|s| s.id == id_or_name || s.name == id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            Ok(data.ssh_keys.remove(idx))
+        })
+    }
+
+    // === Library API ===
+    //
+    // Type-erased wrappers over the per-type operations above, for callers
+    // embedding kookie as a dependency rather than going through the CLI.
+    // `commands::get`/`commands::add`/etc. stay on the per-type methods
+    // directly, since they already know which type they're handling and get
+    // better compiler help (e.g. `Password::FIELDS`) from it.
+
+    /// Adds any kind of secret, dispatching to the matching `add_*` method.
+    #[allow(dead_code)]
+    pub fn add_secret(&mut self, secret: Secret) -> Result<(), VaultError> {
+        match secret {
+            Secret::Password(s) => self.add_password(s),
+            Secret::ApiKey(s) => self.add_api_key(s),
+            Secret::Note(s) => self.add_note(s),
+            Secret::DbCredential(s) => self.add_db_credential(s),
+            Secret::Token(s) => self.add_token(s),
+            Secret::Custom(s) => self.add_custom(s),
+            Secret::SshKey(s) => self.add_ssh_key(s),
+        }
+    }
+
+    /// Looks up a secret of any type by id or name.
+    #[allow(dead_code)]
+    pub fn get_secret(&self, id_or_name: &str) -> Option<Secret> {
+        if let Some(s) = self.get_password(id_or_name) {
+            return Some(Secret::Password(s.clone()));
+        }
+        if let Some(s) = self.get_api_key(id_or_name) {
+            return Some(Secret::ApiKey(s.clone()));
+        }
+        if let Some(s) = self.get_note(id_or_name) {
+            return Some(Secret::Note(s.clone()));
+        }
+        if let Some(s) = self.get_db_credential(id_or_name) {
+            return Some(Secret::DbCredential(s.clone()));
+        }
+        if let Some(s) = self.get_token(id_or_name) {
+            return Some(Secret::Token(s.clone()));
+        }
+        if let Some(s) = self.get_custom(id_or_name) {
+            return Some(Secret::Custom(s.clone()));
+        }
+        if let Some(s) = self.get_ssh_key(id_or_name) {
+            return Some(Secret::SshKey(s.clone()));
+        }
+        None
+    }
+
+    /// Deletes a secret of any type by id or name, dispatching to the
+    /// matching `delete_*` method.
+    #[allow(dead_code)]
+    pub fn delete_secret(&mut self, id_or_name: &str) -> Result<Secret, VaultError> {
+        match self.resolve_prefix(id_or_name) {
+            PrefixResult::Unique(entry) => match entry.secret_type {
+                SecretType::Password => self.delete_password(&entry.id).map(Secret::Password),
+                SecretType::ApiKey => self.delete_api_key(&entry.id).map(Secret::ApiKey),
+                SecretType::Note => self.delete_note(&entry.id).map(Secret::Note),
+                SecretType::DbCredential => self.delete_db_credential(&entry.id).map(Secret::DbCredential),
+                SecretType::Token => self.delete_token(&entry.id).map(Secret::Token),
+                SecretType::Custom => self.delete_custom(&entry.id).map(Secret::Custom),
+                SecretType::SshKey => self.delete_ssh_key(&entry.id).map(Secret::SshKey),
+            },
+            PrefixResult::Ambiguous(_) => Err(VaultError::Invalid(
+                "id_or_name".to_string(),
+                format!("'{}' is an ambiguous id prefix; use a longer prefix or the full id", id_or_name),
+            )),
+            PrefixResult::None => Err(VaultError::SecretNotFound(id_or_name.to_string())),
+        }
+    }
+
+    /// Lists every secret's metadata, optionally narrowed to one type.
+    /// Doesn't expose secret values - use `get_secret` for that.
+    #[allow(dead_code)]
+    pub fn list_secrets(&self, filter: Option<SecretType>) -> Vec<SecretEntry> {
+        let mut entries = self.data.all_entries();
+        if let Some(secret_type) = filter {
+            entries.retain(|e| e.secret_type == secret_type);
+        }
+        entries
+    }
+
+    /// Iterates over every secret across all seven vectors, with full data
+    /// (unlike `list_secrets`, which only exposes metadata).
+    #[allow(dead_code)]
+    pub fn iter_secrets(&self) -> impl Iterator<Item = Secret> + '_ {
+        self.data.secrets()
+    }
+
+    // === Favorite Operations ===
+
+    /// Sets or clears the `favorite` flag on any secret (regardless of
+    /// type), identified by id or name.
+    pub fn set_favorite(&mut self, id_or_name: &str, favorite: bool) -> Result<(), VaultError> {
+        self.mutate(|data| {
+            let flag = data
+                .favorite_mut(id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            *flag = favorite;
+            Ok(())
+        })
+    }
+
+    // === Burn-After-Read Operations ===
+
+    /// Sets or clears the `burn_after_read` flag on any secret (regardless
+    /// of type), identified by id or name.
+    pub fn set_burn_after_read(&mut self, id_or_name: &str, burn: bool) -> Result<(), VaultError> {
+        self.mutate(|data| {
+            let flag = data
+                .burn_after_read_mut(id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            *flag = burn;
+            Ok(())
+        })
+    }
+
+    // === Attachment Operations ===
+
+    /// Sums the decoded size of every attachment already on a secret,
+    /// identified by id or name. Used to enforce a total attachment budget
+    /// per secret, separate from `Attachment::MAX_SIZE_BYTES`'s per-file cap.
+    pub fn attachment_total_bytes(&self, id_or_name: &str) -> Option<u64> {
+        let attachments = self.data.attachments(id_or_name)?;
+        Some(attachments.iter().filter_map(|a| a.decode().ok()).map(|b| b.len() as u64).sum())
+    }
+
+    /// Attaches a file to any secret (regardless of type), identified by
+    /// id or name. Rejects files over `Attachment::MAX_SIZE_BYTES`.
+    pub fn attach(&mut self, id_or_name: &str, filename: String, bytes: &[u8]) -> Result<(), VaultError> {
+        let attachment = Attachment::from_bytes(filename, bytes).map_err(VaultError::AttachmentTooLarge)?;
+        self.mutate(|data| {
+            let attachments = data
+                .attachments_mut(id_or_name)
+                .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
+            attachments.push(attachment);
+            Ok(())
+        })
+    }
+
+    /// Decodes and returns the raw bytes of a named attachment on a secret,
+    /// identified by id or name.
+    pub fn extract_attachment(&self, id_or_name: &str, filename: &str) -> Result<Vec<u8>, VaultError> {
+        let attachments = self
+            .data
+            .attachments(id_or_name)
             .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))?;
-        let removed = self.data.tokens.remove(idx);
-        self.save()?;
-        Ok(removed)
+        let attachment = attachments
+            .iter()
+            .find(|a| a.filename == filename)
+            .ok_or_else(|| VaultError::AttachmentNotFound(filename.to_string()))?;
+        attachment
+            .decode()
+            .map_err(|e| VaultError::AttachmentDecodeError(e.to_string()))
+    }
+
+    // === Trash Operations ===
+
+    /// Moves a secret (of any type) into the trash instead of deleting it
+    /// outright. Returns the secret's name.
+    pub fn trash(&mut self, id_or_name: &str) -> Result<String, VaultError> {
+        self.mutate(|data| {
+            let secret = if let Some(idx) = data.passwords.iter().position(|p| p.id == id_or_name || p.name == id_or_name) {
+                TrashedSecret::Password(data.passwords.remove(idx))
+            } else if let Some(idx) = data.api_keys.iter().position(|k| k.id == id_or_name || k.name == id_or_name) {
+                TrashedSecret::ApiKey(data.api_keys.remove(idx))
+            } else if let Some(idx) = data.notes.iter().position(|n| n.id == id_or_name || n.name == id_or_name) {
+                TrashedSecret::Note(data.notes.remove(idx))
+            } else if let Some(idx) = data.db_credentials.iter().position(|c| c.id == id_or_name || c.name == id_or_name) {
+                TrashedSecret::DbCredential(data.db_credentials.remove(idx))
+            } else if let Some(idx) = data.tokens.iter().position(|t| t.id == id_or_name || t.name == id_or_name) {
+                TrashedSecret::Token(data.tokens.remove(idx))
+            } else if let Some(idx) = data.custom_secrets.iter().position(|c| c.id == id_or_name || c.name == id_or_name) {
+                TrashedSecret::Custom(data.custom_secrets.remove(idx))
+            } else if let Some(idx) = data.ssh_keys.iter().position(|s| s.id == id_or_name || s.name == id_or_name) {
+                TrashedSecret::SshKey(data.ssh_keys.remove(idx))
+            } else {
+                return Err(VaultError::SecretNotFound(id_or_name.to_string()));
+            };
+            let name = secret.name().to_string();
+            data.trash.push(TrashEntry { secret, deleted_at: Utc::now() });
+            Ok(name)
+        })
+    }
+
+    /// Moves a trashed secret back into its original collection. Returns
+    /// the secret's name.
+    pub fn restore(&mut self, id_or_name: &str) -> Result<String, VaultError> {
+        self.mutate(|data| {
+            let idx = data
+                .trash
+                .iter()
+                .position(|e| e.secret.id() == id_or_name || e.secret.name() == id_or_name)
+                .ok_or_else(|| VaultError::TrashedSecretNotFound(id_or_name.to_string()))?;
+            let entry = data.trash.remove(idx);
+            let name = entry.secret.name().to_string();
+            match entry.secret {
+                TrashedSecret::Password(p) => data.passwords.push(p),
+                TrashedSecret::ApiKey(k) => data.api_keys.push(k),
+                TrashedSecret::Note(n) => data.notes.push(n),
+                TrashedSecret::DbCredential(c) => data.db_credentials.push(c),
+                TrashedSecret::Token(t) => data.tokens.push(t),
+                TrashedSecret::Custom(c) => data.custom_secrets.push(c),
+                TrashedSecret::SshKey(s) => data.ssh_keys.push(s),
+            }
+            Ok(name)
+        })
+    }
+
+    /// Permanently removes everything currently in the trash. Returns the
+    /// number of secrets removed.
+    pub fn empty_trash(&mut self) -> Result<usize, VaultError> {
+        self.mutate(|data| {
+            let count = data.trash.len();
+            data.trash.clear();
+            Ok(count)
+        })
+    }
+
+    /// Permanently removes trashed secrets older than `max_age_days`.
+    /// Returns the number of secrets purged. Called best-effort on unlock
+    /// so expiry doesn't depend on a background process.
+    pub fn purge_expired_trash(&mut self, max_age_days: u32) -> Result<usize, VaultError> {
+        self.mutate(|data| {
+            let cutoff = Utc::now() - Duration::days(max_age_days as i64);
+            let before = data.trash.len();
+            data.trash.retain(|e| e.deleted_at >= cutoff);
+            Ok(before - data.trash.len())
+        })
     }
 }
 
@@ -290,3 +1285,326 @@ impl Default for Vault {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn open_at(path: PathBuf, password: &str) -> Vault {
+        let mut vault = Vault {
+            path,
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        vault.unlock(password).unwrap();
+        vault
+    }
+
+    #[test]
+    fn test_concurrent_writers_no_lost_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut init_vault = Vault {
+            path: path.clone(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        init_vault.init("hunter2").unwrap();
+
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let mut vault = open_at(path, "hunter2");
+                    vault
+                        .add_password(
+                            Password::new(
+                                format!("secret-{i}"),
+                                "pw".to_string(),
+                                None,
+                                None,
+                                None,
+                                None,
+                                None,
+                                None,
+                            )
+                            .unwrap(),
+                        )
+                        .unwrap();
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let verify = open_at(path, "hunter2");
+        assert_eq!(verify.data.passwords.len(), 5, "no writer's add should be lost");
+    }
+
+    #[test]
+    fn test_unlock_reconstructs_secrets_under_per_secret_file_backend() {
+        let _guard = storage::KOOKIE_HOME_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KOOKIE_HOME", dir.path());
+
+        let path = dir.path().join("vault.json");
+        let mut vault = Vault {
+            path: path.clone(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::PerSecretFile,
+        normalize_names: false,
+        };
+        vault.init("hunter2").unwrap();
+        vault
+            .add_password(
+                Password::new(
+                    "github".to_string(),
+                    "pw".to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        // Everything so far went through `mutate`/`read_data`. The bug this
+        // guards against is specific to `unlock`'s own fast path, so open a
+        // fresh `Vault` and go through that instead.
+        let mut reopened = Vault {
+            path,
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::PerSecretFile,
+        normalize_names: false,
+        };
+        reopened.unlock("hunter2").unwrap();
+
+        assert_eq!(reopened.data.passwords.len(), 1, "unlock should reconstruct secrets kept in secrets/<id>.enc");
+
+        std::env::remove_var("KOOKIE_HOME");
+    }
+
+    #[test]
+    fn test_open_decrypts_without_prompting() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut init_vault = Vault {
+            path: path.clone(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        init_vault.init("hunter2").unwrap();
+        init_vault
+            .add_note(Note::new("reminder".to_string(), "buy milk".to_string()))
+            .unwrap();
+
+        let vault = Vault::open(&path, "hunter2").unwrap();
+        assert_eq!(vault.data.notes.len(), 1);
+
+        assert!(matches!(Vault::open(&path, "wrong"), Err(VaultError::WrongPassword)));
+    }
+
+    #[test]
+    fn test_large_note_roundtrips_through_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut init_vault = Vault {
+            path: path.clone(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        init_vault.init("hunter2").unwrap();
+
+        let big_content = "x".repeat(COMPRESSION_THRESHOLD_BYTES * 2);
+        init_vault.add_note(Note::new("huge".to_string(), big_content.clone())).unwrap();
+
+        let on_disk = storage::load_vault_file(&path).unwrap();
+        assert!(on_disk.compressed, "a note past the threshold should be stored compressed");
+
+        let vault = Vault::open(&path, "hunter2").unwrap();
+        assert_eq!(vault.data.notes[0].content, big_content);
+    }
+
+    #[test]
+    fn test_resolve_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut vault = Vault {
+            path: path.clone(),
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        vault.init("hunter2").unwrap();
+        vault
+            .add_note(Note::new("ab".to_string(), "shadowed by an id prefix below".to_string()))
+            .unwrap();
+        vault
+            .add_note(Note::new("first".to_string(), "note".to_string()))
+            .unwrap();
+        vault
+            .add_note(Note::new("second".to_string(), "note".to_string()))
+            .unwrap();
+
+        assert!(matches!(vault.resolve_prefix("nope"), PrefixResult::None));
+
+        // Exact name match wins even though "ab" could in principle also be
+        // a prefix of some other secret's (UUID) id.
+        match vault.resolve_prefix("ab") {
+            PrefixResult::Unique(e) => assert_eq!(e.name, "ab"),
+            _ => panic!("expected a unique exact-name match"),
+        }
+
+        let first_id = vault.data.notes[1].id.clone();
+        match vault.resolve_prefix(&first_id[..8]) {
+            PrefixResult::Unique(e) => assert_eq!(e.id, first_id),
+            _ => panic!("expected a unique prefix match"),
+        }
+
+        match vault.resolve_prefix("") {
+            PrefixResult::Ambiguous(matches) => assert_eq!(matches.len(), 3),
+            _ => panic!("an empty prefix matches everything"),
+        }
+    }
+
+    #[test]
+    fn test_burn_after_read_secret_is_gone_on_second_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut vault = Vault {
+            path,
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        vault.init("hunter2").unwrap();
+        vault
+            .add_note(Note::new("self-destruct".to_string(), "read me once".to_string()))
+            .unwrap();
+        vault.set_burn_after_read("self-destruct", true).unwrap();
+
+        // First `get`: the secret is still there and marked for burning -
+        // `kookie get` is responsible for deleting it after showing it.
+        let note = vault.get_note("self-destruct").cloned().unwrap();
+        assert!(note.burn_after_read);
+        vault.delete_note("self-destruct").unwrap();
+
+        // Second `get`: gone for good, same as any other deleted secret.
+        assert!(vault.get_note("self-destruct").is_none());
+    }
+
+    #[test]
+    fn test_library_api_add_get_list_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut vault = Vault {
+            path,
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        vault.init("hunter2").unwrap();
+
+        vault
+            .add_secret(Secret::Note(Note::new("embedded".to_string(), "from a library caller".to_string())))
+            .unwrap();
+
+        let entries = vault.list_secrets(Some(SecretType::Note));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "embedded");
+        assert!(vault.list_secrets(Some(SecretType::Password)).is_empty());
+
+        match vault.get_secret("embedded") {
+            Some(Secret::Note(n)) => assert_eq!(n.content, "from a library caller"),
+            other => panic!("expected a note, got {other:?}"),
+        }
+
+        let deleted = vault.delete_secret("embedded").unwrap();
+        assert_eq!(deleted.secret_type(), SecretType::Note);
+        assert!(vault.get_secret("embedded").is_none());
+    }
+
+    #[test]
+    fn test_iter_secrets_covers_every_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        let mut vault = Vault {
+            path,
+            data: VaultData::default(),
+            key: None,
+            salt: String::new(),
+            kdf_profile: kdf::KdfProfile::default(),
+            hint: None,
+            storage_backend: storage::StorageBackend::default(),
+        normalize_names: false,
+        };
+        vault.init("hunter2").unwrap();
+
+        vault
+            .add_password(Password::new("pw".to_string(), "s3cret".to_string(), None, None, None, None, None, None).unwrap())
+            .unwrap();
+        vault.add_note(Note::new("note".to_string(), "content".to_string())).unwrap();
+
+        let secrets: Vec<Secret> = vault.iter_secrets().collect();
+        assert_eq!(secrets.len(), 2);
+
+        let password = secrets.iter().find(|s| s.name() == "pw").unwrap();
+        assert_eq!(password.primary_value(), Some("s3cret".to_string()));
+
+        let note = secrets.iter().find(|s| s.name() == "note").unwrap();
+        assert_eq!(note.primary_value(), Some("content".to_string()));
+    }
+}
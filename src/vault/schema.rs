@@ -0,0 +1,56 @@
+//! Custom secret schemas
+//!
+//! A schema is just a named list of field definitions - which fields a
+//! `Custom` secret of this kind has, and which of those should be masked
+//! like a password rather than shown in plain. `kookie add --custom
+//! --schema <name>` loads `<name>.json` from `storage::get_schemas_dir()`
+//! and prompts for exactly those fields, instead of the free-form
+//! "enter fields one at a time" loop.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use thiserror::Error;
+
+use super::storage;
+
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("No schema named '{0}' found in {1}")]
+    NotFound(String, String),
+    #[error("IO error reading schema '{0}': {1}")]
+    Io(String, std::io::Error),
+    #[error("Schema '{0}' is not valid JSON: {1}")]
+    Invalid(String, serde_json::Error),
+}
+
+/// A single field definition within a schema.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SchemaField {
+    pub name: String,
+    /// Whether `add --custom --schema` should prompt for this field with
+    /// hidden input and `display_custom` should mask it, same as a
+    /// hand-entered `CustomField { secret: true, .. }`.
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// A named set of field definitions, loaded from
+/// `storage::get_schemas_dir()/<name>.json`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CustomSchema {
+    pub name: String,
+    pub fields: Vec<SchemaField>,
+}
+
+/// Loads the schema named `name` from `<schemas_dir>/<name>.json`.
+pub fn load(name: &str) -> Result<CustomSchema, SchemaError> {
+    let dir = storage::get_schemas_dir();
+    let path = dir.join(format!("{}.json", name));
+
+    if !path.exists() {
+        return Err(SchemaError::NotFound(name.to_string(), dir.display().to_string()));
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| SchemaError::Io(name.to_string(), e))?;
+    serde_json::from_str(&content).map_err(|e| SchemaError::Invalid(name.to_string(), e))
+}
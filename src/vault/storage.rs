@@ -1,11 +1,79 @@
 //! Vault storage operations
 
 use super::{VaultError, VaultFile};
-use std::fs;
-use std::path::PathBuf;
+use fs2::FileExt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-/// Returns the default vault directory path
+/// Where `Vault::save`/`Vault::unlock` keep the seven secret-type vectors.
+///
+/// `Monolithic` is the historical default: everything lives in the single
+/// encrypted `vault.json`. `PerSecretFile` splits each secret into its own
+/// encrypted file under `secrets/<id>.enc` instead, so syncing the vault
+/// directory with git only touches the files for secrets that actually
+/// changed rather than rewriting one big blob on every edit. Selected via
+/// `kookie config set storage_backend <monolithic|per-secret-file>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    #[default]
+    Monolithic,
+    PerSecretFile,
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "monolithic" => Ok(StorageBackend::Monolithic),
+            "per-secret-file" | "per_secret_file" => Ok(StorageBackend::PerSecretFile),
+            other => Err(format!(
+                "Unknown storage backend '{}'. Expected one of: monolithic, per-secret-file",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            StorageBackend::Monolithic => "monolithic",
+            StorageBackend::PerSecretFile => "per-secret-file",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+static HOME_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Overrides the vault directory for the rest of the process, from the
+/// `--home` global flag. Takes precedence over `KOOKIE_HOME` and
+/// `$XDG_DATA_HOME`. Intended to be called once, early in `main`.
+pub fn set_home_override(dir: PathBuf) {
+    let _ = HOME_OVERRIDE.set(dir);
+}
+
+/// Returns the vault directory, resolved in priority order:
+/// 1. The `--home` flag (via `set_home_override`)
+/// 2. `KOOKIE_HOME`
+/// 3. `$XDG_DATA_HOME/kookie`
+/// 4. `~/.kookie`
 pub fn get_vault_dir() -> PathBuf {
+    if let Some(dir) = HOME_OVERRIDE.get() {
+        return dir.clone();
+    }
+
+    if let Ok(dir) = std::env::var("KOOKIE_HOME") {
+        return PathBuf::from(dir);
+    }
+
+    if let Ok(xdg_data_home) = std::env::var("XDG_DATA_HOME") {
+        return PathBuf::from(xdg_data_home).join("kookie");
+    }
+
     dirs::home_dir()
         .expect("Could not find home directory")
         .join(".kookie")
@@ -26,6 +94,94 @@ pub fn get_config_path() -> PathBuf {
     get_vault_dir().join("config.json")
 }
 
+/// Returns the path of the explicit lock sentinel file
+pub fn get_lock_flag_path() -> PathBuf {
+    get_vault_dir().join(".locked")
+}
+
+/// Returns the directory `kookie add --custom --schema <name>` looks in
+/// for `<name>.json` schema files, honoring the same `--home`/`KOOKIE_HOME`
+/// overrides as the vault itself.
+pub fn get_schemas_dir() -> PathBuf {
+    get_vault_dir().join("schemas")
+}
+
+/// Returns the path of the PIN-wrapped session file
+pub fn get_pin_session_path() -> PathBuf {
+    get_vault_dir().join(".pin_session")
+}
+
+/// Returns the path of the advisory lock file guarding concurrent writers
+fn get_vault_lock_path() -> PathBuf {
+    get_vault_dir().join("vault.json.lock")
+}
+
+/// Returns the directory individual secret files live under when
+/// `StorageBackend::PerSecretFile` is selected.
+pub fn get_secrets_dir() -> PathBuf {
+    get_vault_dir().join("secrets")
+}
+
+/// Returns the path of the per-secret-file index, listing which secret ids
+/// exist and what type each is, without requiring every file to be
+/// decrypted just to enumerate them.
+fn get_secrets_index_path() -> PathBuf {
+    get_secrets_dir().join("index.json")
+}
+
+/// Returns the encrypted file path for a single secret, under
+/// `StorageBackend::PerSecretFile`.
+fn get_secret_file_path(id: &str) -> PathBuf {
+    get_secrets_dir().join(format!("{}.enc", id))
+}
+
+/// An advisory file lock held for the duration of a read-modify-write cycle
+/// on `vault.json`, preventing two concurrent `kookie` processes from
+/// clobbering each other's changes. The underlying OS lock is released when
+/// this guard is dropped.
+pub struct VaultLock {
+    _file: File,
+}
+
+/// How long to retry acquiring the lock before giving up
+const LOCK_WAIT: std::time::Duration = std::time::Duration::from_millis(500);
+const LOCK_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+impl VaultLock {
+    /// Acquires the lock, briefly waiting for a concurrent writer to
+    /// finish before failing with "vault is in use".
+    pub fn acquire() -> Result<Self, VaultError> {
+        ensure_vault_dir()?;
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(get_vault_lock_path())?;
+
+        let deadline = std::time::Instant::now() + LOCK_WAIT;
+        loop {
+            match file.try_lock_exclusive() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(_) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(_) => {
+                    return Err(VaultError::IoError(std::io::Error::new(
+                        std::io::ErrorKind::WouldBlock,
+                        "vault is in use (locked by another kookie process)",
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for VaultLock {
+    fn drop(&mut self) {
+        let _ = self._file.unlock();
+    }
+}
+
 /// Ensures the vault directory exists
 pub fn ensure_vault_dir() -> Result<(), VaultError> {
     let dir = get_vault_dir();
@@ -35,17 +191,142 @@ pub fn ensure_vault_dir() -> Result<(), VaultError> {
     Ok(())
 }
 
+/// Warns if `path` is readable or writable by group/other, and offers to
+/// tighten it to `0600` on the spot. The vault's contents are encrypted,
+/// but an overly permissive mode is still a common footgun for local-first
+/// secret stores (e.g. a forgotten `umask`). Shared with `kookie doctor`,
+/// which runs it over every vault-related file.
+#[cfg(unix)]
+pub(crate) fn check_permissions(path: &PathBuf) -> Result<(), VaultError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::metadata(path)?;
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        crate::utils::display::warning(&format!(
+            "{} is readable by group/other (mode {:o}).",
+            path.display(),
+            mode
+        ));
+
+        // Piped/scripted input (e.g. `add --value -`) must not be
+        // consumed by this confirmation instead of reaching the command
+        // that actually asked for it - skip the prompt non-interactively,
+        // same as `get --all --reveal`/`list --reveal` refuse outright.
+        if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            crate::utils::display::info("Run 'kookie doctor' or chmod 600 it by hand.");
+            return Ok(());
+        }
+
+        if crate::utils::input::prompt_confirm("Tighten permissions to 0600 now?", true).unwrap_or(false) {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+            crate::utils::display::success("Permissions tightened to 0600.");
+        }
+    }
+    Ok(())
+}
+
 /// Loads the vault file from disk
 pub fn load_vault_file(path: &PathBuf) -> Result<VaultFile, VaultError> {
+    crate::utils::display::verbose(&format!("Reading vault file: {}", path.display()));
+
+    #[cfg(unix)]
+    check_permissions(path)?;
+
     let content = fs::read_to_string(path)?;
     let vault_file: VaultFile = serde_json::from_str(&content)?;
     Ok(vault_file)
 }
 
 /// Saves the vault file to disk
-pub fn save_vault_file(path: &PathBuf, vault_file: &VaultFile) -> Result<(), VaultError> {
+pub fn save_vault_file(path: &Path, vault_file: &VaultFile) -> Result<(), VaultError> {
     ensure_vault_dir()?;
     let content = serde_json::to_string_pretty(vault_file)?;
-    fs::write(path, content)?;
+    crate::utils::secure_fs::write(path, content.as_bytes())?;
     Ok(())
 }
+
+/// One entry in the `StorageBackend::PerSecretFile` index.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct SecretIndexEntry {
+    pub id: String,
+    pub secret_type: super::types::SecretType,
+}
+
+/// The `StorageBackend::PerSecretFile` index. Stored unencrypted, like
+/// `VaultFile::hint`, so listing which secrets exist doesn't require
+/// decrypting every file up front - the ids and types it holds reveal
+/// nothing about secret contents.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct SecretIndex {
+    pub entries: Vec<SecretIndexEntry>,
+}
+
+/// Loads the per-secret-file index, or an empty one if it doesn't exist yet
+/// (e.g. the backend was just switched on for an empty vault).
+pub fn load_secret_index() -> Result<SecretIndex, VaultError> {
+    let path = get_secrets_index_path();
+    if !path.exists() {
+        return Ok(SecretIndex::default());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Saves the per-secret-file index.
+pub fn save_secret_index(index: &SecretIndex) -> Result<(), VaultError> {
+    fs::create_dir_all(get_secrets_dir())?;
+    let content = serde_json::to_string_pretty(index)?;
+    fs::write(get_secrets_index_path(), content)?;
+    Ok(())
+}
+
+/// Writes a single secret's already-encrypted payload to its own file.
+pub fn save_secret_file(id: &str, encrypted: &str) -> Result<(), VaultError> {
+    fs::create_dir_all(get_secrets_dir())?;
+    let path = get_secret_file_path(id);
+    crate::utils::secure_fs::write(&path, encrypted.as_bytes())?;
+    Ok(())
+}
+
+/// Reads back a single secret's encrypted payload.
+pub fn load_secret_file(id: &str) -> Result<String, VaultError> {
+    Ok(fs::read_to_string(get_secret_file_path(id))?)
+}
+
+/// Deletes a single secret's file. A no-op if it's already gone.
+pub fn delete_secret_file(id: &str) -> Result<(), VaultError> {
+    let path = get_secret_file_path(id);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// `KOOKIE_HOME` is process-global state, but `cargo test` runs tests for
+/// this crate in parallel threads of one process - any test that points it
+/// at a tempdir has to hold this for the duration, or it can race another
+/// such test and briefly resolve every vault path against the wrong
+/// directory. Shared across `vault::tests` and `session::cache::tests` too,
+/// which do the same thing.
+#[cfg(test)]
+pub(crate) static KOOKIE_HOME_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kookie_home_relocates_all_paths() {
+        let _guard = KOOKIE_HOME_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KOOKIE_HOME", dir.path());
+
+        assert_eq!(get_vault_dir(), dir.path());
+        assert_eq!(get_vault_path(), dir.path().join("vault.json"));
+        assert_eq!(get_session_path(), dir.path().join(".session"));
+        assert_eq!(get_config_path(), dir.path().join("config.json"));
+
+        std::env::remove_var("KOOKIE_HOME");
+    }
+}